@@ -1,3 +1,4 @@
+use crate::time_scale::TimeScale;
 use bevy::prelude::*;
 use rand::{thread_rng, Rng};
 use std::{ops::Range, time::Duration};
@@ -60,6 +61,7 @@ impl SheetAnimation {
 fn animate_sheet(
     mut cmd: Commands,
     time: Res<Time>,
+    time_scale: Res<TimeScale>,
     texture_atlases: Res<Assets<TextureAtlas>>,
     mut query: Query<(
         Entity,
@@ -72,7 +74,7 @@ fn animate_sheet(
         let mut run = anim.delay.is_none();
 
         if let Some(delay) = &mut anim.delay {
-            delay.tick(time.delta());
+            delay.tick(time_scale.scale_delta(time.delta()));
 
             if delay.just_finished() {
                 anim.delay = None;
@@ -82,7 +84,7 @@ fn animate_sheet(
         }
 
         if run {
-            anim.timer.tick(time.delta());
+            anim.timer.tick(time_scale.scale_delta(time.delta()));
             if anim.timer.just_finished() {
                 let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
                 let (from, to) = if let Some(ref range) = anim.range {
@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
+use bevy_kira_audio::AudioSource;
 
 use iyes_loopless::prelude::*;
 
-use crate::GameState;
+use crate::{localization::StringTable, settings::Language, GameState};
 
 // todo: add a system which check all sprites/sheets have even dimensions (frame dimensions for sheets) else just panic
 pub struct AssetsPlugin;
@@ -12,25 +13,41 @@ impl Plugin for AssetsPlugin {
         app.add_loopless_state(GameState::Loading)
             .add_loading_state(
                 LoadingState::new(GameState::Loading)
-                    .continue_to_state(GameState::Playing)
+                    .continue_to_state(GameState::Menu)
                     .with_collection::<AudioAssets>()
                     .with_collection::<Sprites>()
-                    .with_collection::<Fonts>(),
+                    .with_collection::<Fonts>()
+                    .with_collection::<StringTables>(),
             );
     }
 }
 
 #[derive(AssetCollection)]
 pub struct AudioAssets {
-    // #[asset(path = "audio/background.ogg")]
-    // single_file: Handle<AudioSource>,
+    #[asset(path = "audio/card_used.ogg")]
+    pub card_used: Handle<AudioSource>,
+
+    #[asset(path = "audio/explosion.ogg")]
+    pub explosion: Handle<AudioSource>,
+
+    #[asset(path = "audio/flying.ogg")]
+    pub flying: Handle<AudioSource>,
+
+    #[asset(path = "audio/order_done.ogg")]
+    pub order_done: Handle<AudioSource>,
+
+    #[asset(path = "audio/order_failed.ogg")]
+    pub order_failed: Handle<AudioSource>,
+
+    #[asset(path = "audio/piece_placed.ogg")]
+    pub piece_placed: Handle<AudioSource>,
+
+    #[asset(path = "audio/tile_click.ogg")]
+    pub tile_click: Handle<AudioSource>,
 }
 
 #[derive(AssetCollection)]
 pub struct Sprites {
-    #[asset(path = "sprites/tutorial.png")]
-    pub tutorial: Handle<Image>,
-
     #[asset(path = "sprites/card.png")]
     pub card: Handle<Image>,
 
@@ -52,6 +69,15 @@ pub struct Sprites {
     #[asset(path = "sprites/hint_tooltip.png")]
     pub hint_tooltip: Handle<Image>,
 
+    #[asset(path = "sprites/trash_bin.png")]
+    pub trash_bin: Handle<Image>,
+
+    #[asset(path = "sprites/trash_bin_outline.png")]
+    pub trash_bin_outline: Handle<Image>,
+
+    #[asset(path = "sprites/soup_bowl.png")]
+    pub soup_bowl: Handle<Image>,
+
     #[asset(path = "sprites/order_tooltip.png")]
     pub order_tooltip: Handle<Image>,
 
@@ -81,6 +107,9 @@ pub struct Sprites {
     #[asset(texture_atlas(tile_size_x = 16., tile_size_y = 16., columns = 6, rows = 1))]
     #[asset(path = "sprites/veggies_sheet.png")]
     pub ingredients: Handle<TextureAtlas>,
+    #[asset(texture_atlas(tile_size_x = 16., tile_size_y = 16., columns = 4, rows = 1))]
+    #[asset(path = "sprites/card_icons_sheet.png")]
+    pub card_icons: Handle<TextureAtlas>,
 
     #[asset(texture_atlas(tile_size_x = 37., tile_size_y = 30., columns = 2, rows = 1))]
     #[asset(path = "sprites/cauldron_sheet.png")]
@@ -123,4 +152,39 @@ pub struct Sprites {
 pub struct Fonts {
     #[asset(path = "fonts/m3x6.ttf")]
     pub tooltip: Handle<Font>,
+
+    #[asset(path = "fonts/m5x7.ttf")]
+    pub m5x7: Handle<Font>,
+
+    #[asset(path = "fonts/FiraSans-Bold.ttf")]
+    pub fira: Handle<Font>,
+}
+
+impl Fonts {
+    /// Font by its name used in the string tables
+    pub fn get(&self, name: &str) -> Handle<Font> {
+        match name {
+            "m5x7" => self.m5x7.clone(),
+            "fira" => self.fira.clone(),
+            _ => self.tooltip.clone(),
+        }
+    }
+}
+
+#[derive(AssetCollection)]
+pub struct StringTables {
+    #[asset(path = "lang/en.lang")]
+    pub en: Handle<StringTable>,
+
+    #[asset(path = "lang/cs.lang")]
+    pub cs: Handle<StringTable>,
+}
+
+impl StringTables {
+    pub fn get_table(&self, language: Language) -> &Handle<StringTable> {
+        match language {
+            Language::English => &self.en,
+            Language::Czech => &self.cs,
+        }
+    }
 }
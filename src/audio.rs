@@ -0,0 +1,163 @@
+use crate::{
+    assets::AudioAssets,
+    card::CardEffect,
+    input::Action,
+    interaction::InteractionEv,
+    level::LevelEv,
+    order::OrderEv,
+    tile_placement::{BoardEv, TileExplosion},
+    ui::ButtonClickEv,
+    GameState,
+};
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use iyes_loopless::prelude::*;
+
+pub struct GameAudioPlugin;
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(AudioPlugin)
+            .add_audio_channel::<UiSfx>()
+            .add_audio_channel::<BoardSfx>()
+            .add_audio_channel::<OrderSfx>()
+            .init_resource::<AudioVolume>()
+            .add_system(apply_sfx_volume)
+            .add_system(toggle_mute)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_not_in_state(GameState::Loading)
+                    .with_system(play_ui_sfx)
+                    .with_system(play_board_sfx)
+                    .with_system(play_order_sfx)
+                    .into(),
+            );
+    }
+}
+
+// sfx categories - each has its own channel so they can be mixed separately
+pub struct UiSfx;
+pub struct BoardSfx;
+pub struct OrderSfx;
+
+const UI_SFX_VOLUME: f64 = 0.5;
+const BOARD_SFX_VOLUME: f64 = 0.7;
+const ORDER_SFX_VOLUME: f64 = 0.9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioVolume {
+    pub master: f64,
+    pub sfx: f64,
+    pub music: f64,
+    pub muted: bool,
+}
+
+impl Default for AudioVolume {
+    fn default() -> Self {
+        Self {
+            master: 1.,
+            sfx: 1.,
+            music: 0.7,
+            muted: false,
+        }
+    }
+}
+
+impl AudioVolume {
+    pub fn get_sfx_volume(&self, category_volume: f64) -> f64 {
+        self.get_master_volume() * self.sfx * category_volume
+    }
+
+    pub fn get_music_volume(&self) -> f64 {
+        self.get_master_volume() * self.music
+    }
+
+    fn get_master_volume(&self) -> f64 {
+        if self.muted {
+            0.
+        } else {
+            self.master
+        }
+    }
+}
+
+fn toggle_mute(actions: Res<Input<Action>>, mut volume: ResMut<AudioVolume>) {
+    if actions.just_pressed(Action::ToggleMute) {
+        volume.muted = !volume.muted;
+    }
+}
+
+fn apply_sfx_volume(
+    volume: Res<AudioVolume>,
+    ui_channel: Res<AudioChannel<UiSfx>>,
+    board_channel: Res<AudioChannel<BoardSfx>>,
+    order_channel: Res<AudioChannel<OrderSfx>>,
+) {
+    if volume.is_changed() {
+        ui_channel.set_volume(volume.get_sfx_volume(UI_SFX_VOLUME));
+        board_channel.set_volume(volume.get_sfx_volume(BOARD_SFX_VOLUME));
+        order_channel.set_volume(volume.get_sfx_volume(ORDER_SFX_VOLUME));
+    }
+}
+
+fn play_ui_sfx(
+    audio: Res<AudioAssets>,
+    channel: Res<AudioChannel<UiSfx>>,
+    mut click_evr: EventReader<ButtonClickEv>,
+    mut interaction_evr: EventReader<InteractionEv>,
+) {
+    let picked_up = interaction_evr
+        .iter()
+        .any(|ev| matches!(ev, InteractionEv::DragStart(_)));
+
+    if click_evr.iter().count() > 0 || picked_up {
+        channel.play(audio.tile_click.clone());
+    }
+}
+
+fn play_board_sfx(
+    audio: Res<AudioAssets>,
+    channel: Res<AudioChannel<BoardSfx>>,
+    mut board_evr: EventReader<BoardEv>,
+    mut card_evr: EventReader<CardEffect>,
+    explosion_q: Query<(), Added<TileExplosion>>,
+) {
+    // a single sound per kind and frame, clears tend to come in batches
+    let (mut placed, mut cleared) = (false, false);
+    for ev in board_evr.iter() {
+        match ev {
+            BoardEv::PiecePlaced => placed = true,
+            // cleared lines spawn cards flying to the hand
+            BoardEv::Cleared(_) => cleared = true,
+        }
+    }
+
+    for (play, sfx) in [
+        (placed, &audio.piece_placed),
+        (cleared, &audio.flying),
+        (card_evr.iter().count() > 0, &audio.card_used),
+        (!explosion_q.is_empty(), &audio.explosion),
+    ] {
+        if play {
+            channel.play(sfx.clone());
+        }
+    }
+}
+
+fn play_order_sfx(
+    audio: Res<AudioAssets>,
+    channel: Res<AudioChannel<OrderSfx>>,
+    mut order_evr: EventReader<OrderEv>,
+    mut lvl_evr: EventReader<LevelEv>,
+) {
+    for ev in order_evr.iter() {
+        if let OrderEv::Completed { .. } = ev {
+            channel.play(audio.order_done.clone());
+        }
+    }
+
+    for ev in lvl_evr.iter() {
+        if let LevelEv::LevelOver { won: false } = ev {
+            channel.play(audio.order_failed.clone());
+        }
+    }
+}
@@ -23,7 +23,7 @@ impl Default for Board {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BoardClear {
     Row(usize),
     Column(usize),
@@ -79,6 +79,18 @@ impl Board {
         }
     }
 
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.heigth
+    }
+
+    pub fn get_section_count(&self) -> usize {
+        self.width * self.heigth / (self.section_size * self.section_size)
+    }
+
     pub fn can_place_piece(&self, x: usize, y: usize, piece: &[usize]) -> Result<(), PlaceError> {
         let mut res = Ok(());
 
@@ -135,6 +147,32 @@ impl Board {
         Ok(cleared)
     }
 
+    /// Clears the piece would cause if placed, without placing it
+    pub fn predict_clears(
+        &self,
+        x: usize,
+        y: usize,
+        piece: &[usize],
+    ) -> Result<Vec<BoardClear>, PlaceError> {
+        let mut board = Self::with_fields(
+            self.width,
+            self.heigth,
+            self.section_size,
+            self.fields.clone(),
+        );
+        board.place_piece(x, y, piece)
+    }
+
+    pub fn get_clear_fields(&self, clear: &BoardClear) -> Vec<usize> {
+        match clear {
+            BoardClear::Row(row) => self.get_row_range(*row).collect(),
+            BoardClear::Column(col) => self.get_column(*col),
+            BoardClear::Section { section_index, .. } => {
+                self.get_section_by_section_index(*section_index)
+            }
+        }
+    }
+
     fn get_column(&self, col: usize) -> Vec<usize> {
         if col >= self.width {
             panic!("Column [{col}] is out of bounds");
@@ -382,6 +420,21 @@ mod tests {
         board.get_section_by_section_index(section_index)
     }
 
+    #[test_case(1, 1 => matches Ok(c) if c == vec![
+        BoardClear::Column(1),
+        BoardClear::Row(1),
+        BoardClear::Section { section_index: 0, used_special: false }
+    ])]
+    #[test_case(0, 0 => matches Err(PlaceError::Taken))]
+    #[test_case(2, 0 => matches Err(PlaceError::OutOfBounds))]
+    fn predict_clears(x: usize, y: usize) -> Result<Vec<BoardClear>, PlaceError> {
+        let board = Board::with_fields(2, 2, 2, [true, true, true, false].into());
+        let clears = board.predict_clears(x, y, &[0]);
+
+        assert_that(&board.fields).is_equal_to(vec![true, true, true, false]);
+        clears
+    }
+
     #[test_case(0 => true)]
     #[test_case(1 => false)]
     fn row_done(row: usize) -> bool {
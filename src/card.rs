@@ -1,26 +1,30 @@
 use crate::{
     assets::Sprites,
     board::{Board, BoardClear},
-    cauldron::{Cauldron, TooltipIngridientList},
+    cauldron::Cauldron,
     drag::{Draggable, Dragged},
     highlight::Highligtable,
+    hint::{Hint, HintEv},
+    input::Action,
     interaction::{Interactable, InteractionEv, InteractionGroup, InteractionState},
-    level::{InteractableSection, LevelEv},
-    list::{ListPlugin, ListPluginOptions},
-    render::{
-        ZIndex, COL_DARK, COL_LIGHT, COL_OUTLINE_HIGHLIGHTED, PADDED_VIEW_EXTENDS, VIEW_PADDING,
-        VIEW_SIZE,
-    },
+    level::{CurrentLevel, InteractableSection, LevelEv, Levels},
+    list::{ListIndex, ListPlugin, ListPluginOptions},
+    localization::Localization,
+    order::{get_hovered_order, Order},
+    render::{ZIndex, PADDED_VIEW_EXTENDS, VIEW_PADDING, VIEW_SIZE},
+    settings::Settings,
+    theme::{Theme, ThemeColor},
     tween::{
         delay_tween, get_relative_move_anim, get_relative_move_by_tween, FadeHierarchyBundle,
         TweenDoneAction,
     },
     GameState,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use bevy_inspector_egui::Inspectable;
 use bevy_tweening::{Animator, EaseFunction};
 use iyes_loopless::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
 
 pub struct CardPlugin;
 impl Plugin for CardPlugin {
@@ -34,6 +38,8 @@ impl Plugin for CardPlugin {
                 shift_duration_ms: 300,
             }))
             .add_system_to_stage(CoreStage::Last, drop_card) // run after update to get precise dragged.origin
+            .add_system(split_card_stack.run_in_state(GameState::Playing))
+            .add_system(update_count_badges)
             .add_system(on_level_over.run_not_in_state(GameState::Loading));
 
         if cfg!(debug_assertions) {
@@ -46,9 +52,27 @@ pub const MAX_CARDS: usize = 5;
 pub const CARD_SIZE: Vec2 = Vec2::new(32., 48.);
 pub const CARD_EXTENDS: Vec2 = Vec2::new(CARD_SIZE.x / 2., CARD_SIZE.y / 2.);
 const CARD_INDEX_X_OFFSET: i32 = -35;
+const DEFAULT_CARDS_PER_CLEAR: usize = 2;
+/// Clears in a single batch that count as a multi-clear
+const MULTI_CLEAR_COUNT: usize = 2;
+/// Keeps the count badge a single digit
+pub const MAX_STACK_SIZE: u8 = 9;
 
+/// Identical ingredient cards stack into a single card
 #[derive(Component, Inspectable)]
-pub struct Card {}
+pub struct Card {
+    pub count: u8,
+}
+
+/// A card dragged off a stack - it isn't in the hand list until it goes back
+#[derive(Component)]
+pub struct StackOrigin {
+    stack_e: Entity,
+    index: usize,
+}
+
+#[derive(Component)]
+struct CardCountBadge;
 
 #[derive(Component, Debug, Inspectable, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Ingredient {
@@ -58,6 +82,8 @@ pub enum Ingredient {
     Eggplant,
     Mushroom,
     Garlic,
+    /// Stands in for any missing ingredient of an order
+    Wildcard,
 }
 
 impl Ingredient {
@@ -69,6 +95,54 @@ impl Ingredient {
             Ingredient::Mushroom => 3,
             Ingredient::Garlic => 4,
             Ingredient::Tomato => 5,
+            Ingredient::Wildcard => CardKind::WILDCARD_ICON_INDEX,
+        }
+    }
+
+    pub fn get_atlas(&self, sprites: &Sprites) -> Handle<TextureAtlas> {
+        match self {
+            Ingredient::Wildcard => sprites.card_icons.clone(),
+            _ => sprites.ingredients.clone(),
+        }
+    }
+
+    pub fn is_rare(&self) -> bool {
+        matches!(
+            self,
+            Ingredient::Eggplant | Ingredient::Mushroom | Ingredient::Garlic
+        )
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardKind {
+    Ingredient(Ingredient),
+    /// Pauses the timer of an order
+    Freeze,
+    /// Takes the last ingredient out of a cauldron
+    Ladle,
+    /// Replaces the pieces in the hand
+    Reroll,
+}
+
+impl CardKind {
+    const WILDCARD_ICON_INDEX: usize = 0;
+
+    pub const SPECIALS: [CardKind; 4] = [
+        CardKind::Ingredient(Ingredient::Wildcard),
+        CardKind::Freeze,
+        CardKind::Ladle,
+        CardKind::Reroll,
+    ];
+
+    fn get_icon(&self, sprites: &Sprites) -> (Handle<TextureAtlas>, usize) {
+        match self {
+            CardKind::Ingredient(ingredient) => {
+                (ingredient.get_atlas(sprites), ingredient.get_sprite_index())
+            }
+            CardKind::Freeze => (sprites.card_icons.clone(), 1),
+            CardKind::Ladle => (sprites.card_icons.clone(), 2),
+            CardKind::Reroll => (sprites.card_icons.clone(), 3),
         }
     }
 }
@@ -81,43 +155,188 @@ pub enum CardEffect {
     Ingredient {
         ingredient: Ingredient,
         cauldron_e: Entity,
+        /// More than 1 when a whole stack is dropped into the cauldron
+        count: u8,
     },
     ClearSection {
         section: usize,
     },
+    FreezeOrder {
+        order_e: Entity,
+    },
+    /// The ingredient has already been taken out of the cauldron
+    RemoveIngredient {
+        ingredient: Ingredient,
+        cauldron_e: Entity,
+    },
+    Reroll,
+}
+
+/// Ingredient of the card spawned for each cleared row, column and section
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngredientMap {
+    pub rows: Vec<Ingredient>,
+    pub columns: Vec<Ingredient>,
+    pub sections: Vec<Ingredient>,
+}
+
+impl Default for IngredientMap {
+    fn default() -> Self {
+        use Ingredient::*;
+
+        Self {
+            rows: vec![
+                Tomato, Tomato, Tomato, Potato, Potato, Potato, Pumpkin, Pumpkin, Pumpkin,
+            ],
+            columns: vec![
+                Pumpkin, Pumpkin, Pumpkin, Potato, Potato, Potato, Tomato, Tomato, Tomato,
+            ],
+            sections: vec![
+                Eggplant, Pumpkin, Potato, Tomato, Mushroom, Pumpkin, Potato, Tomato, Garlic,
+            ],
+        }
+    }
+}
+
+impl IngredientMap {
+    pub fn new(rows: Vec<Ingredient>, columns: Vec<Ingredient>, sections: Vec<Ingredient>) -> Self {
+        Self {
+            rows,
+            columns,
+            sections,
+        }
+    }
+
+    /// Tables shorter than the board wrap around, so a bigger board still gets cards
+    pub fn get_ingredient(&self, clear: &BoardClear) -> Option<Ingredient> {
+        let (table, index) = match clear {
+            BoardClear::Row(row) => (&self.rows, *row),
+            BoardClear::Column(col) => (&self.columns, *col),
+            BoardClear::Section { section_index, .. } => (&self.sections, *section_index),
+        };
+
+        if table.is_empty() {
+            None
+        } else {
+            table.get(index % table.len()).copied()
+        }
+    }
+
+    pub fn validate(&self, board: &Board) -> Result<(), String> {
+        for (name, table, expected) in [
+            ("rows", &self.rows, board.get_height()),
+            ("columns", &self.columns, board.get_width()),
+            ("sections", &self.sections, board.get_section_count()),
+        ] {
+            if table.len() != expected {
+                return Err(format!("{} {name}, the board has {expected}", table.len()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Which cards the clears drop and how often
+#[derive(Debug, Clone)]
+pub struct CardDropTable {
+    pub cards_per_clear: usize,
+    /// Chance (0-1) of each card of the ingredient to drop, the missing ones always drop
+    pub ingredient_weights: HashMap<Ingredient, f32>,
+    /// Chance of a special card for a batch of clears
+    pub multi_clear_special_chance: f32,
+    /// Chance of a special card for a single section clear
+    pub section_special_chance: f32,
+    pub special_weights: Vec<(CardKind, u32)>,
+    /// Special cards per day
+    pub max_special_cards: usize,
+}
+
+impl Default for CardDropTable {
+    fn default() -> Self {
+        Self {
+            cards_per_clear: DEFAULT_CARDS_PER_CLEAR,
+            ingredient_weights: [
+                Ingredient::Eggplant,
+                Ingredient::Mushroom,
+                Ingredient::Garlic,
+            ]
+            .into_iter()
+            .map(|i| (i, 0.75))
+            .collect(),
+            multi_clear_special_chance: 1.,
+            section_special_chance: 0.25,
+            special_weights: vec![
+                (CardKind::Ingredient(Ingredient::Wildcard), 2),
+                (CardKind::Freeze, 3),
+                (CardKind::Ladle, 3),
+                (CardKind::Reroll, 2),
+            ],
+            max_special_cards: 3,
+        }
+    }
+}
+
+impl CardDropTable {
+    pub fn get_ingredient_weight(&self, ingredient: Ingredient) -> f32 {
+        self.ingredient_weights
+            .get(&ingredient)
+            .copied()
+            .unwrap_or(1.)
+    }
+
+    /// Number of cards of the ingredient dropped by a single clear
+    pub fn roll_ingredient_count(&self, ingredient: Ingredient, rng: &mut impl Rng) -> usize {
+        let weight = self.get_ingredient_weight(ingredient);
+        (0..self.cards_per_clear)
+            .filter(|_| rng.gen::<f32>() < weight)
+            .count()
+    }
+
+    /// Special card for a batch of clears - `dropped_count` specials dropped earlier that day
+    pub fn roll_special(
+        &self,
+        clear_count: usize,
+        section_cleared: bool,
+        dropped_count: usize,
+        rng: &mut impl Rng,
+    ) -> Option<CardKind> {
+        if dropped_count >= self.max_special_cards {
+            return None;
+        }
+
+        let chance = if clear_count >= MULTI_CLEAR_COUNT {
+            self.multi_clear_special_chance
+        } else if section_cleared {
+            self.section_special_chance
+        } else {
+            0.
+        };
+
+        if rng.gen::<f32>() >= chance {
+            return None;
+        }
+
+        let dist = WeightedIndex::new(self.special_weights.iter().map(|(_, w)| *w)).ok()?;
+        Some(self.special_weights[dist.sample(rng)].0)
+    }
 }
 
-pub fn spawn_card(cmd: &mut Commands, sprites: &Sprites, clear: &BoardClear) {
+pub fn spawn_card(
+    cmd: &mut Commands,
+    sprites: &Sprites,
+    theme: &Theme,
+    kind: CardKind,
+    count: u8,
+) -> Entity {
     let corner = CARD_SIZE / 2.;
-    let ingredient = match clear {
-        BoardClear::Row(row) => match row {
-            0..=2 => Ingredient::Tomato,
-            3..=5 => Ingredient::Potato,
-            6..=8 => Ingredient::Pumpkin,
-            _ => unimplemented!("Unknown ingredient for row {row}"),
-        },
-        BoardClear::Column(col) => match col {
-            0..=2 => Ingredient::Pumpkin,
-            3..=5 => Ingredient::Potato,
-            6..=8 => Ingredient::Tomato,
-            _ => unimplemented!("Unknown ingredient for column {col}"),
-        },
-        BoardClear::Section { section_index, .. } => match section_index {
-            0 => Ingredient::Eggplant,
-            1 | 5 => Ingredient::Pumpkin,
-            2 | 6 => Ingredient::Potato,
-            3 | 7 => Ingredient::Tomato,
-            4 => Ingredient::Mushroom,
-            8 => Ingredient::Garlic,
-            _ => unimplemented!("Unknown ingredient for section {section_index}"),
-        },
-    };
+    let (icon_atlas, icon_index) = kind.get_icon(sprites);
 
     let outline_e = cmd
         .spawn_bundle(SpriteBundle {
             texture: sprites.card_outline.clone(),
             sprite: Sprite {
-                color: COL_DARK,
+                color: theme.get(ThemeColor::Dark),
                 ..default()
             },
             ..default()
@@ -137,42 +356,40 @@ pub fn spawn_card(cmd: &mut Commands, sprites: &Sprites, clear: &BoardClear) {
         ..default()
     })
     .insert(ZIndex::Card)
-    .insert(Card {})
-    .insert(ingredient)
+    .insert(Card { count })
+    .insert(kind)
     .insert(Interactable::new_rectangle(InteractionGroup::Card, corner))
     .insert(Draggable { offset: true })
     .insert(Name::new("Card"))
     .insert(Highligtable {
         sprite_e: Some(outline_e),
-        hightlight_color: COL_LIGHT,
-        hover_color: COL_OUTLINE_HIGHLIGHTED,
-        normal_color: COL_DARK,
+        hightlight_color: ThemeColor::Light,
+        hover_color: ThemeColor::Highlighted,
+        normal_color: ThemeColor::Dark,
         drag_groups: vec![],
     })
     .add_child(outline_e)
     .with_children(|b| {
         b.spawn_bundle(SpriteSheetBundle {
-            texture_atlas: sprites.ingredients.clone(),
-            sprite: TextureAtlasSprite::new(ingredient.get_sprite_index()),
+            texture_atlas: icon_atlas,
+            sprite: TextureAtlasSprite::new(icon_index),
             transform: Transform::from_translation(Vec2::new(0., 10.).extend(0.0)),
             ..default()
         });
-    });
+    })
+    .id()
 }
 
-fn test_card_spawn(mut cmd: Commands, mut lvl_evr: EventReader<LevelEv>, sprites: Res<Sprites>) {
+fn test_card_spawn(
+    mut cmd: Commands,
+    mut lvl_evr: EventReader<LevelEv>,
+    sprites: Res<Sprites>,
+    theme: Res<Theme>,
+) {
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelStart = ev {
-            for i in 0..4 {
-                // spawn_card(&mut cmd, &sprites, &BoardClear::Column(0));
-                spawn_card(
-                    &mut cmd,
-                    &sprites,
-                    &BoardClear::Section {
-                        section_index: i,
-                        used_special: false,
-                    },
-                );
+            for kind in CardKind::SPECIALS {
+                spawn_card(&mut cmd, &sprites, &theme, kind, 1);
             }
 
             break;
@@ -180,91 +397,235 @@ fn test_card_spawn(mut cmd: Commands, mut lvl_evr: EventReader<LevelEv>, sprites
     }
 }
 
+/// Dragging a stack takes a single card off it, unless the whole stack modifier is held
+fn split_card_stack(
+    mut cmd: Commands,
+    mut interaction_evr: EventReader<InteractionEv>,
+    actions: Res<Input<Action>>,
+    sprites: Res<Sprites>,
+    theme: Res<Theme>,
+    mut card_q: Query<(&mut Card, &CardKind, &ListIndex<Card>)>,
+) {
+    for ev in interaction_evr.iter() {
+        if let InteractionEv::DragStart(drag_data) = ev
+            && !actions.pressed(Action::WholeStack)
+            && let Ok((mut card, kind, index)) = card_q.get_mut(drag_data.e)
+            && card.count > 1
+        {
+            // the rest of the stack takes over the list slot
+            let stack_e = spawn_card(&mut cmd, &sprites, &theme, *kind, card.count - 1);
+            cmd.entity(stack_e)
+                .insert(Transform::from_translation(drag_data.origin.extend(0.)))
+                .insert(ListIndex::<Card>::new(index.get()));
+
+            card.count = 1;
+            cmd.entity(drag_data.e)
+                .remove::<ListIndex<Card>>()
+                .insert(StackOrigin {
+                    stack_e,
+                    index: index.get(),
+                });
+        }
+    }
+}
+
+fn update_count_badges(
+    mut cmd: Commands,
+    loc: Localization,
+    theme: Res<Theme>,
+    card_q: Query<(Entity, &Card, &Children), Changed<Card>>,
+    mut badge_q: Query<&mut Text, With<CardCountBadge>>,
+) {
+    for (card_e, card, children) in card_q.iter() {
+        let txt = if card.count > 1 {
+            format!("{}x", card.count)
+        } else {
+            String::new()
+        };
+
+        if let Some(mut badge) = children.iter().find_map(|e| badge_q.get_mut(*e).ok()) {
+            badge.sections[0].value = txt;
+        } else if card.count > 1 {
+            cmd.entity(card_e).with_children(|b| {
+                b.spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        txt,
+                        TextStyle {
+                            font: loc.get_font(),
+                            font_size: 16.0,
+                            color: theme.get(ThemeColor::Dark),
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0., -9., 0.01),
+                    ..default()
+                })
+                .insert(CardCountBadge);
+            });
+        }
+    }
+}
+
 fn drop_card(
     mut cmd: Commands,
-    dragged_query: Query<(&Ingredient, &Transform), With<Card>>,
+    dragged_query: Query<(&CardKind, &Transform, Option<&StackOrigin>), With<Card>>,
+    mut card_q: Query<&mut Card>,
     interaction_state: Res<InteractionState>,
     parent_q: Query<&Parent>,
     mut cauldron_q: Query<&mut Cauldron>,
     section_q: Query<&InteractableSection>,
-    tooltip_q: Query<&TooltipIngridientList>,
+    order_q: Query<(Entity, &Parent), With<Order>>,
     mut interaction_evr: EventReader<InteractionEv>,
     mut card_evw: EventWriter<CardEffect>,
+    mut hint_evw: EventWriter<HintEv>,
     board: Res<Board>,
+    lvl: Res<CurrentLevel>,
+    lvls: Res<Levels>,
+    settings: Res<Settings>,
 ) {
     for ev in interaction_evr.iter() {
-        if let InteractionEv::DragEnd(drag_data) = ev {
-            if let Ok((ingredient, card_t)) = dragged_query.get(drag_data.e) {
-                let mut used = false;
+        if let InteractionEv::DragEnd(drag_data) | InteractionEv::DragCancel(drag_data) = ev {
+            if let Ok((kind, card_t, stack_origin)) = dragged_query.get(drag_data.e) {
+                // only a whole stack can have more than 1 card here
+                let count = card_q.get(drag_data.e).map_or(1, |c| c.count);
+                let mut used_count = 0;
+                let hovered_cauldron_e = interaction_state
+                    .get_first_hovered_entity(&InteractionGroup::Cauldron)
+                    .and_then(|e| parent_q.get(e).ok())
+                    .map(|p| p.get());
 
-                if let Some(e) = interaction_state.get_first_hovered_entity(&InteractionGroup::Fire)
+                if matches!(ev, InteractionEv::DragCancel(_)) {
+                    // goes back to the hand
+                } else if interaction_state
+                    .get_first_hovered_entity(&InteractionGroup::Discard)
+                    .is_some()
                 {
-                    if let Ok(cauldron_e) = parent_q.get(e) {
-                        if let Ok(_c) = cauldron_q.get_mut(cauldron_e.get()) {
-                            // increase fire boost
-                            card_evw.send(CardEffect::FireBoost {
-                                cauldron_e: cauldron_e.get(),
-                                boost_dur_multiplier: None,
-                            });
-                            used = true;
+                    used_count = count;
+                } else if let CardKind::Ingredient(ingredient) = kind {
+                    if let Some(e) =
+                        interaction_state.get_first_hovered_entity(&InteractionGroup::Fire)
+                    {
+                        if let Ok(cauldron_e) = parent_q.get(e) {
+                            if let Ok(_c) = cauldron_q.get_mut(cauldron_e.get()) {
+                                // increase fire boost
+                                card_evw.send(CardEffect::FireBoost {
+                                    cauldron_e: cauldron_e.get(),
+                                    boost_dur_multiplier: None,
+                                });
+                                used_count = 1;
+                            }
                         }
-                    }
-                } else if let Some(e) =
-                    interaction_state.get_first_hovered_entity(&InteractionGroup::Cauldron)
-                {
-                    if let Ok(cauldron_e) = parent_q.get(e) {
-                        if let Ok(mut c) = cauldron_q.get_mut(cauldron_e.get()) {
-                            // there can't be a ready meal in the cauldron
-                            let mut can_use_ingredient = true;
-
-                            if let Some(tooltip_e) = c.tooltip_e {
-                                if let Ok(tooltip) = tooltip_q.get(tooltip_e) {
-                                    if tooltip.ingredients.len() >= 3
-                                        && !tooltip.ingredients.contains_key(&(*ingredient as u8))
-                                    {
-                                        can_use_ingredient = false;
-                                    }
+                    } else if let Some(cauldron_e) = hovered_cauldron_e {
+                        // there can't be a ready meal in the cauldron
+                        if let Ok(mut c) = cauldron_q.get_mut(cauldron_e) && c.soup_e.is_none() {
+                            let rules = &lvls[lvl.level_index].recipe_rules;
+                            match rules.check_add(&c.ingredients, *ingredient, count, c.capacity) {
+                                Ok(()) => {
+                                    c.ingredients.extend(
+                                        std::iter::repeat(*ingredient).take(count as usize),
+                                    );
+                                    card_evw.send(CardEffect::Ingredient {
+                                        cauldron_e,
+                                        ingredient: *ingredient,
+                                        count,
+                                    });
+
+                                    used_count = count;
+                                }
+                                Err(rule) => {
+                                    hint_evw.send(HintEv {
+                                        hint: rule.get_hint(),
+                                        target_e: cauldron_e,
+                                    });
                                 }
                             }
-
-                            if can_use_ingredient {
-                                c.ingredients.push(*ingredient);
-                                card_evw.send(CardEffect::Ingredient {
-                                    cauldron_e: cauldron_e.get(),
-                                    ingredient: *ingredient,
+                        }
+                    } else if let Some(e) =
+                        interaction_state.get_first_hovered_entity(&InteractionGroup::GridSection)
+                    {
+                        if let Ok(section) = section_q.get(e) {
+                            if !board.is_section_empty(section.0) {
+                                card_evw.send(CardEffect::ClearSection { section: section.0 });
+                                used_count = 1;
+                            } else {
+                                hint_evw.send(HintEv {
+                                    hint: Hint::EmptySection,
+                                    target_e: e,
                                 });
-
-                                used = true;
                             }
                         }
                     }
-                } else if let Some(e) =
-                    interaction_state.get_first_hovered_entity(&InteractionGroup::GridSection)
-                {
-                    if let Ok(section) = section_q.get(e) {
-                        if !board.is_section_empty(section.0) {
-                            card_evw.send(CardEffect::ClearSection { section: section.0 });
-                            used = true;
-                        }
+                } else if *kind == CardKind::Freeze {
+                    if let Some(order_e) = get_hovered_order(&interaction_state, &order_q) {
+                        card_evw.send(CardEffect::FreezeOrder { order_e });
+                        used_count = 1;
                     }
-                };
+                } else if *kind == CardKind::Ladle {
+                    if let Some(cauldron_e) = hovered_cauldron_e
+                        && let Ok(mut c) = cauldron_q.get_mut(cauldron_e)
+                        && c.soup_e.is_none()
+                        && let Some(ingredient) = c.ingredients.pop()
+                    {
+                        card_evw.send(CardEffect::RemoveIngredient {
+                            ingredient,
+                            cauldron_e,
+                        });
+                        used_count = 1;
+                    }
+                } else if *kind == CardKind::Reroll
+                    && interaction_state
+                        .get_first_hovered_entity(&InteractionGroup::Piece)
+                        .is_some()
+                {
+                    card_evw.send(CardEffect::Reroll);
+                    used_count = 1;
+                }
 
-                if used {
+                let e = drag_data.e;
+                if used_count >= count {
                     // todo: particles?
-                    let mut cmd_e = cmd.entity(drag_data.e);
+                    // not a card anymore while it fades, so nothing stacks onto it
+                    let mut cmd_e = cmd.entity(e);
+                    cmd_e.remove::<Card>();
+                    cmd_e.remove::<ListIndex<Card>>();
                     cmd_e.remove::<Interactable>();
                     cmd_e.insert_bundle(
                         FadeHierarchyBundle::new(false, 300, Color::NONE)
                             .with_done_action(TweenDoneAction::DespawnRecursive),
                     );
+                } else if let Some(origin) = stack_origin
+                    && let Ok(mut stack) = card_q.get_mut(origin.stack_e)
+                {
+                    // merges back into the stack
+                    stack.count += count - used_count;
+                    let mut e_cmd = cmd.entity(e);
+                    e_cmd.remove::<Card>();
+                    e_cmd.remove::<Interactable>();
+                    e_cmd.insert(get_relative_move_anim(
+                        drag_data.origin.extend(card_t.translation.z),
+                        300,
+                        settings.reduced_motion,
+                        Some(TweenDoneAction::DespawnRecursive),
+                    ));
                 } else {
-                    let mut e_cmd = cmd.entity(drag_data.e);
+                    if let Ok(mut card) = card_q.get_mut(e) {
+                        card.count -= used_count;
+                    }
+
+                    let mut e_cmd = cmd.entity(e);
                     e_cmd.remove::<Dragged>();
                     e_cmd.insert(get_relative_move_anim(
                         drag_data.origin.extend(card_t.translation.z),
                         300,
+                        settings.reduced_motion,
                         None,
                     ));
+
+                    // the stack is gone, so the card takes its place
+                    if let Some(origin) = stack_origin {
+                        e_cmd.remove::<StackOrigin>();
+                        e_cmd.insert(ListIndex::<Card>::new(origin.index));
+                    }
                 }
             }
         }
@@ -275,6 +636,7 @@ fn on_level_over(
     mut cmd: Commands,
     mut lvl_evr: EventReader<LevelEv>,
     card_q: Query<Entity, With<Card>>,
+    settings: Res<Settings>,
 ) {
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelOver { .. } = ev {
@@ -284,6 +646,7 @@ fn on_level_over(
                     get_relative_move_by_tween(
                         Vec3::Y * CARD_SIZE.y * 1.5,
                         350,
+                        settings.reduced_motion,
                         EaseFunction::QuadraticIn,
                         Some(TweenDoneAction::DespawnRecursive),
                     ),
@@ -298,3 +661,72 @@ fn on_level_over(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use test_case::test_case;
+
+    #[test_case(BoardClear::Row(4) => Some(Ingredient::Potato))]
+    #[test_case(BoardClear::Column(8) => Some(Ingredient::Tomato))]
+    #[test_case(BoardClear::Section { section_index: 4, used_special: false } => Some(Ingredient::Mushroom))]
+    #[test_case(BoardClear::Row(9) => Some(Ingredient::Tomato) ; "wraps around")]
+    fn get_ingredient(clear: BoardClear) -> Option<Ingredient> {
+        IngredientMap::default().get_ingredient(&clear)
+    }
+
+    #[test]
+    fn get_ingredient_empty_table() {
+        let map = IngredientMap::new(Vec::new(), vec![Ingredient::Garlic], Vec::new());
+
+        assert_eq!(None, map.get_ingredient(&BoardClear::Row(0)));
+        assert_eq!(
+            Some(Ingredient::Garlic),
+            map.get_ingredient(&BoardClear::Column(5))
+        );
+    }
+
+    #[test_case(Ingredient::Tomato, 0. => 2 ; "common ingredients always drop")]
+    #[test_case(Ingredient::Garlic, 1. => 2)]
+    #[test_case(Ingredient::Garlic, 0. => 0)]
+    fn roll_ingredient_count(ingredient: Ingredient, garlic_weight: f32) -> usize {
+        let mut table = CardDropTable::default();
+        table
+            .ingredient_weights
+            .insert(Ingredient::Garlic, garlic_weight);
+
+        table.roll_ingredient_count(ingredient, &mut StdRng::seed_from_u64(0))
+    }
+
+    #[test_case(2, false, 0 => true)]
+    #[test_case(1, false, 0 => false)]
+    #[test_case(1, true, 0 => true)]
+    #[test_case(3, true, 1 => false ; "over the day limit")]
+    fn roll_special(clear_count: usize, section_cleared: bool, dropped_count: usize) -> bool {
+        let table = CardDropTable {
+            section_special_chance: 1.,
+            special_weights: vec![(CardKind::Freeze, 1)],
+            max_special_cards: 1,
+            ..default()
+        };
+
+        table
+            .roll_special(
+                clear_count,
+                section_cleared,
+                dropped_count,
+                &mut StdRng::seed_from_u64(0),
+            )
+            .is_some()
+    }
+
+    #[test_case(9, 9, 3 => true)]
+    #[test_case(12, 12, 3 => false)]
+    #[test_case(9, 9, 9 => false)]
+    fn validate(width: usize, height: usize, section_size: usize) -> bool {
+        IngredientMap::default()
+            .validate(&Board::new(width, height, section_size))
+            .is_ok()
+    }
+}
@@ -1,20 +1,27 @@
 use crate::{
     anim::SheetAnimation,
-    assets::{Fonts, Sprites},
+    assets::Sprites,
     card::{CardEffect, Ingredient},
+    drag::Draggable,
     highlight::Highligtable,
-    interaction::{Interactable, InteractionGroup},
-    level::LevelEv,
-    order::{Order, OrderEv},
+    hint::{Hint, HintEv},
+    interaction::{Interactable, InteractionEv, InteractionGroup, InteractionState},
+    level::{CurrentLevel, LevelEv, Levels},
+    localization::Localization,
+    order::{get_hovered_order, Order, OrderEv},
     progress::TooltipProgress,
-    render::{
-        ZIndex, COL_DARK, COL_DARKER, COL_OUTLINE_HIGHLIGHTED, COL_OUTLINE_HIGHLIGHTED_2,
-        COL_OUTLINE_HOVERED_DRAG,
-    },
+    render::ZIndex,
+    save::SaveData,
+    settings::Settings,
+    shop::upgrade_cauldron,
+    theme::{Theme, ThemeColor},
+    time_scale::{TimeScale, TimeScaled},
     tween::{
         get_relative_fade_text_anim, get_relative_move_anim, get_relative_move_by_anim,
-        get_relative_spritesheet_color_anim, FadeHierarchy, FadeHierarchyBundle, TweenDoneAction,
+        get_relative_sprite_color_anim, get_relative_spritesheet_color_anim, FadeHierarchy,
+        FadeHierarchyBundle, TweenDoneAction,
     },
+    ui::Overlay,
     GameState,
 };
 use bevy::{prelude::*, utils::HashMap};
@@ -25,34 +32,160 @@ pub struct CauldronPlugin;
 impl Plugin for CauldronPlugin {
     fn build(&self, app: &mut App) {
         app.add_exit_system(GameState::Loading, setup)
+            .add_enter_system(GameState::Playing, respawn_cauldrons)
             .add_system_set(
                 ConditionSet::new()
                     .run_not_in_state(GameState::Loading)
                     .with_system(show_progress_tooltip)
                     .with_system(add_ingredient_to_tooltip)
+                    .with_system(remove_ingredient_from_tooltip)
                     .with_system(boost_fire)
+                    .with_system(serve_soup)
                     .with_system(on_level_over)
                     .into(),
             )
-            .add_system(cook)
+            .add_system(cook.run_in_state(Overlay::None))
+            .add_system(spoil_soup.run_in_state(Overlay::None).after(cook))
             .add_system(set_fire_intensity.after(cook));
     }
 }
 
-// pub const COOK_TIME: f32 = 1.;
-pub const COOK_TIME: f32 = 15.;
 pub const FIRE_BOOST_TIME: f32 = 15.;
 pub const FIRE_BOOST_MULT: f32 = 2.5;
 const TOOLTIP_TWEEN_OFFSET: f32 = 28.;
+const TOOLTIP_WIDTH: f32 = 51.;
+/// Lifts every other tooltip when the cauldrons are too close for them
+const TOOLTIP_STAGGER: f32 = 30.;
+const CAULDRON_Y: f32 = -44.;
+/// Right of the trash bin
+const ROW_START_X: f32 = 20.;
+const ROW_END_X: f32 = 138.;
+const ROW_MAX_SPACING: f32 = 54.;
+pub const MAX_CAULDRONS: usize = 4;
+/// Kinds of ingredients - the cauldron tooltip has room for 3
+pub const DEFAULT_CAPACITY: usize = 3;
+/// Tooltip slots get squeezed up to this, a day's 4 kinds & the capacity upgrade
+pub const MAX_CAPACITY: usize = 5;
 const CAULDRON_INGREDIENT_Y: f32 = -4.5;
+const SOUP_SPOIL_TIME: f32 = 20.;
+/// In front of the cauldron's rim
+const SOUP_OFFSET: Vec3 = Vec3::new(0., 6., 0.);
+
+/// Cauldron of a day, with its fire pit
+#[derive(Debug, Clone, Copy)]
+pub struct CauldronSettings {
+    pub x: f32,
+    pub capacity: usize,
+    /// Multiplies the cook time progress
+    pub cook_speed: f32,
+    /// Seconds added by a card thrown into the fire
+    pub fire_boost_time: f32,
+}
+
+impl CauldronSettings {
+    pub fn new(x: f32) -> Self {
+        Self {
+            x,
+            capacity: DEFAULT_CAPACITY,
+            cook_speed: 1.,
+            fire_boost_time: FIRE_BOOST_TIME,
+        }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_cook_speed(mut self, cook_speed: f32) -> Self {
+        self.cook_speed = cook_speed;
+        self
+    }
+
+    /// Evenly spaced cauldrons right of the board
+    pub fn row(count: usize) -> Vec<Self> {
+        let count = count.clamp(1, MAX_CAULDRONS);
+        let spacing = match count {
+            1 => 0.,
+            _ => ((ROW_END_X - ROW_START_X) / (count - 1) as f32).min(ROW_MAX_SPACING),
+        };
+
+        (0..count)
+            .map(|i| Self::new(ROW_START_X + i as f32 * spacing))
+            .collect()
+    }
+}
 
 #[derive(Component)]
 pub struct Cauldron {
     pub ingredients: Vec<Ingredient>,
+    /// Kinds of ingredients that fit in
+    pub capacity: usize,
+    pub cook_speed: f32,
+    pub fire_boost_time: f32,
+    tooltip_y: f32,
     pub cook_timer: Timer,
+    /// Cooking done on a boosted fire, too much of it burns the soup
+    boosted_cook_s: f32,
     pub fire_boost: Timer,
+    /// The fire flares up after a burn, that boost doesn't count as boosted cooking
+    penalty_boost: bool,
     pub fire_e: Entity,
     pub tooltip_e: Option<Entity>,
+    /// Cooked soup waiting to be served - nothing else cooks until then
+    pub soup_e: Option<Entity>,
+}
+
+impl Cauldron {
+    /// Ticks the fire and the cooking, returns the boosted share of a soup that just finished
+    fn tick(&mut self, delta: Duration, cook_time: f32) -> Option<f32> {
+        self.fire_boost.tick(delta);
+
+        // there's smt. to cook and the previous soup has been served
+        if self.ingredients.is_empty() || self.soup_e.is_some() {
+            return None;
+        }
+
+        let boosted = !self.fire_boost.finished();
+        let mult = if boosted { FIRE_BOOST_MULT } else { 1. };
+        self.cook_timer
+            .set_duration(Duration::from_secs_f32(cook_time));
+        let cook_delta = delta.mul_f32(mult * self.cook_speed);
+        self.cook_timer.tick(cook_delta);
+        if boosted && !self.penalty_boost {
+            self.boosted_cook_s += cook_delta.as_secs_f32();
+        }
+
+        if self.cook_timer.just_finished() {
+            let boosted_share = self.boosted_cook_s / cook_time;
+            self.boosted_cook_s = 0.;
+            Some(boosted_share)
+        } else {
+            None
+        }
+    }
+
+    /// Adds to what's left of the current boost
+    fn boost_fire(&mut self, boost_dur_multiplier: Option<f32>) {
+        let dur = self
+            .fire_boost
+            .duration()
+            .saturating_sub(self.fire_boost.elapsed())
+            .saturating_add(Duration::from_secs_f32(
+                self.fire_boost_time * boost_dur_multiplier.unwrap_or(1.),
+            ));
+        self.fire_boost.set_duration(dur);
+        self.fire_boost.reset();
+        // only the cards thrown in by the player count toward burning
+        self.penalty_boost = boost_dur_multiplier.is_some();
+    }
+}
+
+/// Served by dragging it to an order, it spoils if it waits too long
+#[derive(Component)]
+pub struct Soup {
+    cauldron_e: Entity,
+    spoil_timer: Timer,
 }
 
 pub struct TooltipIngredient {
@@ -70,26 +203,21 @@ pub fn spawn_tooltip_ingredient(
     ingredient: Ingredient,
     count: u8,
     current_list_len: usize,
+    slot_count: usize,
     y_offset: f32,
     cmd: &mut Commands,
     sprites: &Sprites,
-    fonts: &Fonts,
+    loc: &Localization,
+    theme: &Theme,
 ) -> (Entity, Entity) {
-    let x_offset = 16.;
-
-    let x = match current_list_len {
-        0 => -x_offset,
-        1 => 0.,
-        2 => x_offset,
-        _ => panic!("Too many items"),
-    };
+    let x = get_slot_x(current_list_len, slot_count);
 
     let txt_e = cmd
         .spawn_bundle(Text2dBundle {
             text: Text::from_section(
                 format!("{count}x"),
                 TextStyle {
-                    font: fonts.tooltip.clone(),
+                    font: loc.get_font(),
                     font_size: 16.0,
                     color: Color::NONE,
                 },
@@ -99,14 +227,15 @@ pub fn spawn_tooltip_ingredient(
             ..default()
         })
         .insert(get_relative_fade_text_anim(
-            // todo: from res/const
-            COL_DARK, 400, None,
+            theme.get(ThemeColor::Dark),
+            400,
+            None,
         ))
         .id();
 
     let tooltip_e = cmd
         .spawn_bundle(SpriteSheetBundle {
-            texture_atlas: sprites.ingredients.clone(),
+            texture_atlas: ingredient.get_atlas(sprites),
             sprite: TextureAtlasSprite {
                 color: Color::NONE,
                 index: ingredient.get_sprite_index(),
@@ -123,19 +252,73 @@ pub fn spawn_tooltip_ingredient(
     (tooltip_e, txt_e)
 }
 
-fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
-    for (x, firepit_x, sprite_index, flip_x, fire_x) in
-        [(20., -1., 0, false, 0.), (74., -5., 1, true, -1.0)].iter()
-    {
+/// Slots are centered in the tooltip and get closer when there's more of them
+fn get_slot_x(index: usize, slot_count: usize) -> f32 {
+    let spacing = match slot_count {
+        0..=DEFAULT_CAPACITY => 16.,
+        4 => 12.,
+        _ => 9.,
+    };
+
+    (index as f32 - (slot_count as f32 - 1.) / 2.) * spacing
+}
+
+/// Cauldrons of the main menu backdrop
+fn setup(mut cmd: Commands, sprites: Res<Sprites>, theme: Res<Theme>) {
+    spawn_cauldrons(&mut cmd, &sprites, &theme, &CauldronSettings::row(2));
+}
+
+fn respawn_cauldrons(
+    mut cmd: Commands,
+    sprites: Res<Sprites>,
+    theme: Res<Theme>,
+    lvl: Res<CurrentLevel>,
+    lvls: Res<Levels>,
+    save: Res<SaveData>,
+    cauldron_q: Query<Entity, Or<(With<Cauldron>, With<Soup>)>>,
+) {
+    for e in cauldron_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+
+    let settings: Vec<_> = lvls[lvl.level_index]
+        .cauldrons
+        .iter()
+        .map(|c| upgrade_cauldron(*c, &save))
+        .collect();
+    spawn_cauldrons(&mut cmd, &sprites, &theme, &settings);
+}
+
+fn spawn_cauldrons(
+    cmd: &mut Commands,
+    sprites: &Sprites,
+    theme: &Theme,
+    settings: &[CauldronSettings],
+) {
+    let mut prev_x = None;
+    for (i, cauldron) in settings.iter().take(MAX_CAULDRONS).enumerate() {
+        // the sheets have a regular and a mirrored variant
+        let (firepit_x, sprite_index, flip_x, fire_x) = match i % 2 {
+            0 => (-1., 0, false, 0.),
+            _ => (-5., 1, true, -1.),
+        };
+        let tooltip_y = match prev_x {
+            Some(prev_x) if i % 2 == 1 && cauldron.x - prev_x < TOOLTIP_WIDTH => {
+                TOOLTIP_TWEEN_OFFSET + TOOLTIP_STAGGER
+            }
+            _ => TOOLTIP_TWEEN_OFFSET,
+        };
+        prev_x = Some(cauldron.x);
+
         let fire_e = cmd
             .spawn_bundle(SpriteSheetBundle {
                 texture_atlas: sprites.fire.clone(),
                 sprite: TextureAtlasSprite {
-                    index: *sprite_index,
-                    flip_x: *flip_x,
+                    index: sprite_index,
+                    flip_x,
                     ..default()
                 },
-                transform: Transform::from_xyz(*fire_x, -6., 0.01),
+                transform: Transform::from_xyz(fire_x, -6., 0.01),
                 ..default()
             })
             .insert(SheetAnimation::new(100).with_range(0..8, true))
@@ -146,8 +329,8 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
             .spawn_bundle(SpriteSheetBundle {
                 texture_atlas: sprites.cauldron_outline.clone(),
                 sprite: TextureAtlasSprite {
-                    index: *sprite_index,
-                    color: COL_DARKER,
+                    index: sprite_index,
+                    color: theme.get(ThemeColor::Darker),
                     ..default()
                 },
                 ..default()
@@ -159,8 +342,8 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
             .spawn_bundle(SpriteSheetBundle {
                 texture_atlas: sprites.firepit_outline.clone(),
                 sprite: TextureAtlasSprite {
-                    index: *sprite_index,
-                    color: COL_DARKER,
+                    index: sprite_index,
+                    color: theme.get(ThemeColor::Darker),
                     ..default()
                 },
                 ..default()
@@ -170,17 +353,25 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
 
         cmd.spawn_bundle(SpriteSheetBundle {
             texture_atlas: sprites.cauldron.clone(),
-            sprite: TextureAtlasSprite::new(*sprite_index),
-            transform: Transform::from_xyz(*x, -44., 0.5),
+            sprite: TextureAtlasSprite::new(sprite_index),
+            transform: Transform::from_xyz(cauldron.x, CAULDRON_Y, 0.5),
             ..default()
         })
         .insert(ZIndex::Cauldron)
         .insert(Cauldron {
             ingredients: Vec::with_capacity(10),
-            cook_timer: Timer::new(Duration::from_secs_f32(COOK_TIME), true),
+            capacity: cauldron.capacity,
+            cook_speed: cauldron.cook_speed,
+            fire_boost_time: cauldron.fire_boost_time,
+            tooltip_y,
+            // the duration depends on the ingredients
+            cook_timer: Timer::new(Duration::ZERO, true),
+            boosted_cook_s: 0.,
             fire_boost: Timer::default(),
+            penalty_boost: false,
             fire_e,
             tooltip_e: None,
+            soup_e: None,
         })
         .insert(Name::new("Cauldron"))
         .add_child(fire_e)
@@ -188,8 +379,8 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
         .with_children(|b| {
             b.spawn_bundle(SpriteSheetBundle {
                 texture_atlas: sprites.firepit.clone(),
-                sprite: TextureAtlasSprite::new(*sprite_index),
-                transform: Transform::from_xyz(*firepit_x, -25., -0.01),
+                sprite: TextureAtlasSprite::new(sprite_index),
+                transform: Transform::from_xyz(firepit_x, -25., -0.01),
                 ..default()
             })
             .add_child(firepit_outline_e);
@@ -201,7 +392,7 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
                     18.,
                     InteractionGroup::Cauldron,
                     cauldron_outline_e,
-                    COL_OUTLINE_HIGHLIGHTED,
+                    ThemeColor::Highlighted,
                 ),
                 (
                     -28.,
@@ -209,7 +400,7 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
                     16.,
                     InteractionGroup::Fire,
                     firepit_outline_e,
-                    COL_OUTLINE_HIGHLIGHTED_2,
+                    ThemeColor::Highlighted2,
                 ),
             ] {
                 let corner = Vec2::new(corner_x, corner_y);
@@ -221,8 +412,8 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
                 .insert(Highligtable {
                     sprite_e: Some(outline_e),
                     hightlight_color: highlight_col,
-                    hover_color: COL_OUTLINE_HOVERED_DRAG,
-                    normal_color: COL_DARK,
+                    hover_color: ThemeColor::HoveredDrag,
+                    normal_color: ThemeColor::Dark,
                     drag_groups: vec![InteractionGroup::Card],
                 });
             }
@@ -232,42 +423,37 @@ fn setup(mut cmd: Commands, sprites: Res<Sprites>) {
 
 fn cook(
     mut cmd: Commands,
-    mut cauldron_q: Query<(Entity, &mut Cauldron)>,
+    mut cauldron_q: Query<(Entity, &mut Cauldron, &Transform)>,
     mut progress_q: Query<&mut TooltipProgress>,
-    order_q: Query<(Entity, &Order)>,
+    sprites: Res<Sprites>,
     time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    lvl: Res<CurrentLevel>,
+    lvls: Res<Levels>,
     mut order_evw: EventWriter<OrderEv>,
     mut card_evw: EventWriter<CardEffect>,
+    settings: Res<Settings>,
 ) {
-    for (c_e, mut c) in cauldron_q.iter_mut() {
-        c.fire_boost.tick(time.delta());
-
-        // there's smt. to cook
-        if !c.ingredients.is_empty() {
-            let mult = if c.fire_boost.finished() {
-                1.
+    let delta = time_scale.scale_delta(time.delta());
+    let rules = &lvls[lvl.level_index].recipe_rules;
+    for (c_e, mut c, c_t) in cauldron_q.iter_mut() {
+        let cook_time = rules.get_cook_time(&c.ingredients);
+        if let Some(boosted_share) = c.tick(delta, cook_time) {
+            if rules.is_burned(&c.ingredients, boosted_share) {
+                burn_soup(
+                    &mut cmd,
+                    c_e,
+                    &mut c,
+                    &mut order_evw,
+                    &mut card_evw,
+                    settings.reduced_motion,
+                );
             } else {
-                FIRE_BOOST_MULT
-            };
-            c.cook_timer.tick(time.delta().mul_f32(mult));
-
-            if c.cook_timer.just_finished() {
-                if let Some((order_e, _)) = order_q
-                    .iter()
-                    .find(|(_order_e, o)| o.is_equal(&c.ingredients))
-                {
-                    // complete order
-                    order_evw.send(OrderEv::Completed(order_e));
-                } else {
-                    // burn the invalid soup
-                    card_evw.send(CardEffect::FireBoost {
-                        cauldron_e: c_e,
-                        boost_dur_multiplier: Some(2.),
-                    });
-                }
-
-                clear_cauldron_ingredients(&mut cmd, &mut c);
-            } else if let Some(tooltip_e) = c.tooltip_e {
+                let soup_e = spawn_soup(&mut cmd, &sprites, c_e, c_t.translation + SOUP_OFFSET);
+                c.soup_e = Some(soup_e);
+            }
+        } else if !c.ingredients.is_empty() && c.soup_e.is_none() {
+            if let Some(tooltip_e) = c.tooltip_e {
                 if let Ok(mut p) = progress_q.get_mut(tooltip_e) {
                     p.value = c.cook_timer.percent();
                 }
@@ -276,13 +462,67 @@ fn cook(
     }
 }
 
-fn clear_cauldron_ingredients(cmd: &mut Commands, cauldron: &mut Cauldron) {
+fn spawn_soup(cmd: &mut Commands, sprites: &Sprites, cauldron_e: Entity, pos: Vec3) -> Entity {
+    cmd.spawn_bundle(SpriteBundle {
+        texture: sprites.soup_bowl.clone(),
+        sprite: Sprite {
+            color: Color::NONE,
+            ..default()
+        },
+        transform: Transform::from_translation(pos),
+        ..default()
+    })
+    .insert(ZIndex::Card)
+    .insert(Soup {
+        cauldron_e,
+        spoil_timer: Timer::from_seconds(SOUP_SPOIL_TIME, false),
+    })
+    .insert(Interactable::new_rectangle(
+        InteractionGroup::Soup,
+        Vec2::splat(8.),
+    ))
+    .insert(Draggable { offset: false })
+    .insert(get_relative_sprite_color_anim(Color::WHITE, 300, None))
+    .insert(TimeScaled)
+    .insert(Name::new("Soup"))
+    .id()
+}
+
+/// Overcooked and spoiled soups flare up the fire
+fn burn_soup(
+    cmd: &mut Commands,
+    cauldron_e: Entity,
+    cauldron: &mut Cauldron,
+    order_evw: &mut EventWriter<OrderEv>,
+    card_evw: &mut EventWriter<CardEffect>,
+    reduced_motion: bool,
+) {
+    order_evw.send(OrderEv::SoupBurned(cauldron_e));
+    card_evw.send(CardEffect::FireBoost {
+        cauldron_e,
+        boost_dur_multiplier: Some(2.),
+    });
+
+    clear_cauldron_ingredients(cmd, cauldron, reduced_motion);
+}
+
+fn clear_cauldron_ingredients(cmd: &mut Commands, cauldron: &mut Cauldron, reduced_motion: bool) {
+    if let Some(soup_e) = cauldron.soup_e.take() {
+        let mut soup_cmd_e = cmd.entity(soup_e);
+        soup_cmd_e.remove::<Interactable>();
+        soup_cmd_e.insert_bundle(
+            FadeHierarchyBundle::new(false, 300, Color::NONE)
+                .with_done_action(TweenDoneAction::DespawnRecursive),
+        );
+    }
+
     if let Some(tooltip_e) = cauldron.tooltip_e {
         let mut tooltip_cmd_e = cmd.entity(tooltip_e);
         tooltip_cmd_e.insert(FadeHierarchy::new(false, 350, Color::NONE));
         tooltip_cmd_e.insert(get_relative_move_by_anim(
             Vec3::Y * -TOOLTIP_TWEEN_OFFSET,
             400,
+            reduced_motion,
             Some(TweenDoneAction::DespawnRecursive),
         ));
         cauldron.tooltip_e = None;
@@ -290,29 +530,121 @@ fn clear_cauldron_ingredients(cmd: &mut Commands, cauldron: &mut Cauldron) {
     }
 }
 
+fn serve_soup(
+    mut cmd: Commands,
+    mut interaction_evr: EventReader<InteractionEv>,
+    interaction_state: Res<InteractionState>,
+    soup_q: Query<(&Soup, &Transform)>,
+    mut cauldron_q: Query<&mut Cauldron>,
+    order_q: Query<(Entity, &Parent), With<Order>>,
+    order_data_q: Query<(&Order, &Parent)>,
+    lvl: Res<CurrentLevel>,
+    lvls: Res<Levels>,
+    mut order_evw: EventWriter<OrderEv>,
+    mut hint_evw: EventWriter<HintEv>,
+    settings: Res<Settings>,
+) {
+    for ev in interaction_evr.iter() {
+        if let InteractionEv::DragEnd(drag_data) | InteractionEv::DragCancel(drag_data) = ev
+            && let Ok((soup, soup_t)) = soup_q.get(drag_data.e)
+            && let Ok(mut c) = cauldron_q.get_mut(soup.cauldron_e)
+            && c.soup_e == Some(drag_data.e)
+        {
+            let hovered_order = match ev {
+                InteractionEv::DragEnd(_) => get_hovered_order(&interaction_state, &order_q),
+                _ => None,
+            };
+
+            if let Some(order_e) = hovered_order
+                && let Ok((o, tooltip)) = order_data_q.get(order_e)
+            {
+                let quality = o.get_quality(&c.ingredients);
+                if quality >= lvls[lvl.level_index].min_soup_quality {
+                    order_evw.send(OrderEv::Completed { order_e, quality });
+                    clear_cauldron_ingredients(&mut cmd, &mut c, settings.reduced_motion);
+                    continue;
+                }
+
+                hint_evw.send(HintEv {
+                    hint: Hint::WrongSoup,
+                    target_e: tooltip.get(),
+                });
+            }
+
+            // back to the cauldron
+            cmd.entity(drag_data.e).insert(get_relative_move_anim(
+                drag_data.origin.extend(soup_t.translation.z),
+                300,
+                settings.reduced_motion,
+                None,
+            ));
+        }
+    }
+}
+
+fn spoil_soup(
+    mut cmd: Commands,
+    mut soup_q: Query<&mut Soup>,
+    mut cauldron_q: Query<&mut Cauldron>,
+    mut progress_q: Query<&mut TooltipProgress>,
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    mut order_evw: EventWriter<OrderEv>,
+    mut card_evw: EventWriter<CardEffect>,
+    settings: Res<Settings>,
+) {
+    let delta = time_scale.scale_delta(time.delta());
+    for mut soup in soup_q.iter_mut() {
+        if let Ok(mut c) = cauldron_q.get_mut(soup.cauldron_e) {
+            if c.soup_e.is_none() {
+                // served or burned already
+            } else if soup.spoil_timer.tick(delta).just_finished() {
+                burn_soup(
+                    &mut cmd,
+                    soup.cauldron_e,
+                    &mut c,
+                    &mut order_evw,
+                    &mut card_evw,
+                    settings.reduced_motion,
+                );
+            } else if let Some(tooltip_e) = c.tooltip_e
+                && let Ok(mut p) = progress_q.get_mut(tooltip_e)
+            {
+                // the bar runs out with the spoil time
+                p.value = soup.spoil_timer.percent_left();
+            }
+        }
+    }
+}
+
 fn show_progress_tooltip(
     mut cmd: Commands,
     mut card_evr: EventReader<CardEffect>,
     mut cauldron_q: Query<&mut Cauldron>,
     sprites: Res<Sprites>,
-    fonts: Res<Fonts>,
+    loc: Localization,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
 ) {
     for ev in card_evr.iter() {
         if let CardEffect::Ingredient {
             ingredient,
             cauldron_e,
+            count,
         } = ev
         {
             if let Ok(mut c) = cauldron_q.get_mut(*cauldron_e) {
                 if c.tooltip_e.is_none() {
                     let (ingredient_e, ingridient_txt_e) = spawn_tooltip_ingredient(
                         *ingredient,
-                        1,
+                        *count,
                         0,
+                        c.capacity,
                         CAULDRON_INGREDIENT_Y,
                         &mut cmd,
                         &sprites,
-                        &fonts,
+                        &loc,
+                        &theme,
                     );
 
                     cmd.entity(*cauldron_e).with_children(|b| {
@@ -320,7 +652,7 @@ fn show_progress_tooltip(
                         ingredient_list.ingredients.insert(
                             *ingredient as u8,
                             TooltipIngredient {
-                                count: 1,
+                                count: *count,
                                 entity: ingredient_e,
                                 text_e: ingridient_txt_e,
                             },
@@ -337,12 +669,18 @@ fn show_progress_tooltip(
                             })
                             .insert(ZIndex::Tooltip)
                             .insert(Name::new("Tooltip"))
+                            .insert(TimeScaled)
                             .insert(TooltipProgress::new(0., false))
                             .insert(ingredient_list)
-                            .insert_bundle(FadeHierarchyBundle::new(true, 450, COL_DARK))
+                            .insert_bundle(FadeHierarchyBundle::new(
+                                true,
+                                450,
+                                theme.get(ThemeColor::Dark),
+                            ))
                             .insert(get_relative_move_anim(
-                                Vec3::new(0., TOOLTIP_TWEEN_OFFSET, 0.01),
+                                Vec3::new(0., c.tooltip_y, 0.01),
                                 550,
+                                settings.reduced_motion,
                                 None,
                             ))
                             .add_child(ingredient_e)
@@ -358,7 +696,8 @@ fn show_progress_tooltip(
 fn add_ingredient_to_tooltip(
     mut cmd: Commands,
     sprites: Res<Sprites>,
-    fonts: Res<Fonts>,
+    loc: Localization,
+    theme: Res<Theme>,
     mut card_evr: EventReader<CardEffect>,
     cauldron_q: Query<&Cauldron>,
     mut tooltip_ingredient_q: Query<&mut TooltipIngridientList>,
@@ -368,6 +707,7 @@ fn add_ingredient_to_tooltip(
         if let CardEffect::Ingredient {
             ingredient,
             cauldron_e,
+            count,
         } = ev
         {
             if let Ok(c) = cauldron_q.get(*cauldron_e) {
@@ -376,18 +716,20 @@ fn add_ingredient_to_tooltip(
                         if let Some(tooltip_ingredient) =
                             ingredient_list.ingredients.get_mut(&(*ingredient as u8))
                         {
-                            tooltip_ingredient.count += 1;
+                            tooltip_ingredient.count += count;
                             let mut txt = txt_q.get_mut(tooltip_ingredient.text_e).unwrap();
                             txt.sections[0].value = format!("{}x", tooltip_ingredient.count);
                         } else {
                             let (ingredient_e, ingredient_txt_e) = spawn_tooltip_ingredient(
                                 *ingredient,
-                                1,
+                                *count,
                                 ingredient_list.ingredients.len(),
+                                c.capacity,
                                 CAULDRON_INGREDIENT_Y,
                                 &mut cmd,
                                 &sprites,
-                                &fonts,
+                                &loc,
+                                &theme,
                             );
 
                             cmd.entity(c.tooltip_e.unwrap()).add_child(ingredient_e);
@@ -395,7 +737,7 @@ fn add_ingredient_to_tooltip(
                             ingredient_list.ingredients.insert(
                                 *ingredient as u8,
                                 TooltipIngredient {
-                                    count: 1,
+                                    count: *count,
                                     entity: ingredient_e,
                                     text_e: ingredient_txt_e,
                                 },
@@ -408,6 +750,75 @@ fn add_ingredient_to_tooltip(
     }
 }
 
+fn remove_ingredient_from_tooltip(
+    mut cmd: Commands,
+    sprites: Res<Sprites>,
+    loc: Localization,
+    theme: Res<Theme>,
+    mut card_evr: EventReader<CardEffect>,
+    mut cauldron_q: Query<&mut Cauldron>,
+    mut tooltip_ingredient_q: Query<&mut TooltipIngridientList>,
+    settings: Res<Settings>,
+) {
+    for ev in card_evr.iter() {
+        if let CardEffect::RemoveIngredient { cauldron_e, .. } = ev
+            && let Ok(mut c) = cauldron_q.get_mut(*cauldron_e)
+        {
+            if c.ingredients.is_empty() {
+                c.cook_timer.reset();
+                c.boosted_cook_s = 0.;
+                clear_cauldron_ingredients(&mut cmd, &mut c, settings.reduced_motion);
+            } else if let Some(tooltip_e) = c.tooltip_e
+                && let Ok(mut ingredient_list) = tooltip_ingredient_q.get_mut(tooltip_e)
+            {
+                // icon positions depend on the order, so they're all respawned
+                for (_, tooltip_ingredient) in ingredient_list.ingredients.drain() {
+                    cmd.entity(tooltip_ingredient.entity).despawn_recursive();
+                }
+
+                for (i, (ingredient, count)) in
+                    count_ingredients(&c.ingredients).into_iter().enumerate()
+                {
+                    let (ingredient_e, ingredient_txt_e) = spawn_tooltip_ingredient(
+                        ingredient,
+                        count,
+                        i,
+                        c.capacity,
+                        CAULDRON_INGREDIENT_Y,
+                        &mut cmd,
+                        &sprites,
+                        &loc,
+                        &theme,
+                    );
+
+                    cmd.entity(tooltip_e).add_child(ingredient_e);
+                    ingredient_list.ingredients.insert(
+                        ingredient as u8,
+                        TooltipIngredient {
+                            count,
+                            entity: ingredient_e,
+                            text_e: ingredient_txt_e,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Ingredient counts in the order of the first appearance
+fn count_ingredients(ingredients: &[Ingredient]) -> Vec<(Ingredient, u8)> {
+    let mut counts: Vec<(Ingredient, u8)> = Vec::new();
+    for ingredient in ingredients {
+        match counts.iter_mut().find(|(i, _)| i == ingredient) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*ingredient, 1)),
+        }
+    }
+
+    counts
+}
+
 fn boost_fire(mut cauldron_q: Query<&mut Cauldron>, mut card_evr: EventReader<CardEffect>) {
     for ev in card_evr.iter() {
         if let CardEffect::FireBoost {
@@ -416,14 +827,7 @@ fn boost_fire(mut cauldron_q: Query<&mut Cauldron>, mut card_evr: EventReader<Ca
         } = ev
         {
             if let Ok(mut c) = cauldron_q.get_mut(*cauldron_e) {
-                let dur = c
-                    .fire_boost
-                    .duration()
-                    .saturating_add(Duration::from_secs_f32(
-                        FIRE_BOOST_TIME * boost_dur_multiplier.unwrap_or(1.),
-                    ));
-                c.fire_boost.set_duration(dur);
-                c.fire_boost.reset();
+                c.boost_fire(*boost_dur_multiplier);
             }
         }
     }
@@ -465,6 +869,7 @@ fn on_level_over(
     mut cmd: Commands,
     mut lvl_evr: EventReader<LevelEv>,
     mut cauldron_q: Query<&mut Cauldron>,
+    settings: Res<Settings>,
 ) {
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelOver { .. } = ev {
@@ -473,10 +878,82 @@ fn on_level_over(
                 // end fire boosts
                 c.fire_boost.reset();
                 c.fire_boost.set_duration(Duration::from_secs(1));
-                clear_cauldron_ingredients(&mut cmd, &mut c);
+                clear_cauldron_ingredients(&mut cmd, &mut c, settings.reduced_motion);
             }
 
             break;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::RecipeRules;
+    use test_case::test_case;
+
+    #[test_case(vec![] => Vec::<(Ingredient, u8)>::new())]
+    #[test_case(vec![Ingredient::Tomato, Ingredient::Tomato] => vec![(Ingredient::Tomato, 2)])]
+    #[test_case(vec![Ingredient::Potato, Ingredient::Tomato, Ingredient::Potato] => vec![(Ingredient::Potato, 2), (Ingredient::Tomato, 1)])]
+    fn count_ingredients(ingredients: Vec<Ingredient>) -> Vec<(Ingredient, u8)> {
+        super::count_ingredients(&ingredients)
+    }
+
+    #[test_case(0, 3 => -16.)]
+    #[test_case(2, 3 => 16.)]
+    #[test_case(0, 1 => 0.)]
+    #[test_case(3, 4 => 18.)]
+    fn get_slot_x(index: usize, slot_count: usize) -> f32 {
+        super::get_slot_x(index, slot_count)
+    }
+
+    #[test_case(1 => vec![20.])]
+    #[test_case(2 => vec![20., 74.])]
+    #[test_case(3 => vec![20., 74., 128.])]
+    #[test_case(4 => vec![20., 59., 99., 138.])]
+    #[test_case(9 => vec![20., 59., 99., 138.] ; "clamped")]
+    fn row(count: usize) -> Vec<f32> {
+        CauldronSettings::row(count)
+            .iter()
+            .map(|c| c.x.round())
+            .collect()
+    }
+
+    /// Cooks a tomato soup and returns whether it burned
+    fn cook_tomato_soup(c: &mut Cauldron, rules: &RecipeRules) -> bool {
+        c.ingredients = vec![Ingredient::Tomato];
+        let cook_time = rules.get_cook_time(&c.ingredients);
+        loop {
+            if let Some(boosted_share) = c.tick(Duration::from_millis(100), cook_time) {
+                c.ingredients.clear();
+                return rules.is_burned(&[Ingredient::Tomato], boosted_share);
+            }
+        }
+    }
+
+    #[test_case(None => (true, false) ; "player boost")]
+    #[test_case(Some(2.) => (false, false) ; "penalty boost")]
+    fn burn_then_next_soup(boost_dur_multiplier: Option<f32>) -> (bool, bool) {
+        let rules = RecipeRules::default();
+        let mut c = Cauldron {
+            ingredients: Vec::new(),
+            capacity: DEFAULT_CAPACITY,
+            cook_speed: 1.,
+            fire_boost_time: FIRE_BOOST_TIME,
+            tooltip_y: 0.,
+            cook_timer: Timer::new(Duration::ZERO, true),
+            boosted_cook_s: 0.,
+            fire_boost: Timer::default(),
+            penalty_boost: false,
+            fire_e: Entity::from_raw(0),
+            tooltip_e: None,
+            soup_e: None,
+        };
+
+        c.boost_fire(boost_dur_multiplier);
+        let first_burned = cook_tomato_soup(&mut c, &rules);
+        // burn_soup flares up the fire
+        c.boost_fire(Some(2.));
+        (first_burned, cook_tomato_soup(&mut c, &rules))
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    input::{Action, ActionMap, Binding, RawInput},
+    localization::Localization,
+    render::ZIndex,
+    theme::{Theme, ThemeColor},
+    ui::{spawn_button, spawn_panel, spawn_text, ButtonClickEv, Overlay, MENU_LINE_HEIGHT},
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use strum::IntoEnumIterator;
+
+pub struct ControlsPlugin;
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(Overlay::Controls, spawn_controls_menu)
+            .add_exit_system(Overlay::Controls, despawn_controls_menu)
+            .add_system(update_binding_texts.run_in_state(Overlay::Controls))
+            .add_system(on_controls_button_click.run_in_state(Overlay::Controls))
+            // runs after the clicks so the click which started the rebinding can't be captured
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                capture_binding
+                    .run_in_state(Overlay::Controls)
+                    .run_if_resource_exists::<Rebinding>(),
+            );
+    }
+}
+
+const ACTION_X: f32 = -110.;
+const BINDING_X: f32 = -40.;
+const LIST_TOP_Y: f32 = 34.;
+
+#[derive(Component)]
+struct ControlsMenu;
+
+#[derive(Component, Clone, Copy)]
+enum ControlsButton {
+    Binding(Action),
+    Reset,
+    Back,
+}
+
+struct Rebinding {
+    action: Action,
+    armed: bool,
+}
+
+fn spawn_controls_menu(
+    mut cmd: Commands,
+    loc: Localization,
+    map: Res<ActionMap>,
+    theme: Res<Theme>,
+) {
+    let mut children = vec![spawn_text(
+        &mut cmd,
+        &loc,
+        loc.get("menu.controls"),
+        Vec3::new(0., LIST_TOP_Y + MENU_LINE_HEIGHT * 1.5, 0.1),
+        TextAlignment::CENTER,
+        ThemeColor::Light,
+    )];
+
+    for (i, action) in Action::iter().filter(Action::is_rebindable).enumerate() {
+        let y = LIST_TOP_Y - i as f32 * MENU_LINE_HEIGHT;
+
+        children.push(spawn_text(
+            &mut cmd,
+            &loc,
+            loc.get(action.get_key()),
+            Vec3::new(ACTION_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            ThemeColor::Light,
+        ));
+
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            map.get_label(action),
+            Vec3::new(BINDING_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            150.,
+            ThemeColor::Light,
+            true,
+        );
+        cmd.entity(btn_e).insert(ControlsButton::Binding(action));
+        children.push(btn_e);
+    }
+
+    let action_count = Action::iter().filter(Action::is_rebindable).count();
+    let bottom_y = LIST_TOP_Y - (action_count as f32 + 1.) * MENU_LINE_HEIGHT;
+    for (x, btn, text) in [
+        (-40., ControlsButton::Reset, "menu.reset"),
+        (40., ControlsButton::Back, "menu.back"),
+    ] {
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            loc.get(text),
+            Vec3::new(x, bottom_y, 0.1),
+            TextAlignment::CENTER,
+            50.,
+            ThemeColor::Light,
+            true,
+        );
+        cmd.entity(btn_e).insert(btn);
+        children.push(btn_e);
+    }
+
+    let panel_e = spawn_panel(
+        &mut cmd,
+        &theme,
+        Vec2::new(250., 130.),
+        ZIndex::Overlay,
+        "controls_menu",
+        &children,
+    );
+    cmd.entity(panel_e).insert(ControlsMenu);
+}
+
+fn despawn_controls_menu(mut cmd: Commands, menu_q: Query<Entity, With<ControlsMenu>>) {
+    for e in menu_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+    cmd.remove_resource::<Rebinding>();
+}
+
+fn on_controls_button_click(
+    mut cmd: Commands,
+    mut click_evr: EventReader<ButtonClickEv>,
+    button_q: Query<&ControlsButton>,
+    mut map: ResMut<ActionMap>,
+    game_state: Res<CurrentState<GameState>>,
+    rebinding: Option<Res<Rebinding>>,
+) {
+    for ev in click_evr.iter() {
+        // the click could be the key that's being bound
+        if rebinding.is_some() {
+            continue;
+        }
+
+        if let Ok(btn) = button_q.get(ev.0) {
+            match btn {
+                ControlsButton::Binding(action) => cmd.insert_resource(Rebinding {
+                    action: *action,
+                    armed: false,
+                }),
+                ControlsButton::Reset => *map = ActionMap::default(),
+                ControlsButton::Back => {
+                    cmd.insert_resource(NextState(if game_state.0 == GameState::Playing {
+                        Overlay::Pause
+                    } else {
+                        Overlay::None
+                    }))
+                }
+            }
+
+            break;
+        }
+    }
+}
+
+fn capture_binding(
+    mut cmd: Commands,
+    mut rebinding: ResMut<Rebinding>,
+    mut map: ResMut<ActionMap>,
+    kb_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    pad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+) {
+    if !rebinding.armed {
+        rebinding.armed = true;
+        return;
+    }
+
+    if let Some(binding) = Binding::get_just_pressed(&RawInput {
+        kb: &kb_input,
+        mouse: &mouse_input,
+        pad: &pad_input,
+        gamepads: &gamepads,
+    }) {
+        map.rebind(rebinding.action, binding);
+        cmd.remove_resource::<Rebinding>();
+    }
+}
+
+fn update_binding_texts(
+    map: Res<ActionMap>,
+    rebinding: Option<Res<Rebinding>>,
+    loc: Localization,
+    mut button_q: Query<(&ControlsButton, &mut Text)>,
+) {
+    for (btn, mut txt) in button_q.iter_mut() {
+        if let ControlsButton::Binding(action) = btn {
+            let label = match &rebinding {
+                Some(rebinding) if rebinding.action == *action => loc.get("controls.press_key"),
+                _ => map.get_label(*action),
+            };
+
+            if txt.sections[0].value != label {
+                txt.sections[0].value = label;
+            }
+        }
+    }
+}
@@ -3,11 +3,13 @@ use crate::{
     drag::Dragged,
     interaction::Interactable,
     mouse::CursorWorldPosition,
+    nav::is_mouse_control,
     piece::Piece,
-    tile_placement::{Pieces, BOARD_SHIFT, BOARD_SIZE, TILE_SIZE},
+    tile_placement::{Pieces, BOARD_SHIFT, BOARD_SIZE, BOARD_SIZE_PX, TILE_SIZE},
 };
 use bevy::prelude::*;
 use bevy_inspector_egui::Inspectable;
+use iyes_loopless::prelude::*;
 
 use std::ops::{Add, Div};
 
@@ -16,13 +18,16 @@ impl Plugin for CoordsPlugin {
     fn build(&self, app: &mut App) {
         app
             // .add_system(log_coords)
-            .add_system(update_tile_coords);
+            .add_system(update_tile_coords.run_if(is_mouse_control));
     }
 }
 
 #[derive(Component, Debug, Default, Inspectable)]
 pub struct TileCoords {
+    /// Placeable position
     pub tile_coords: Option<UVec2>,
+    /// Position over the board regardless of whether the piece can be placed
+    pub hovered_tile: Option<IVec2>,
 }
 
 pub fn get_tile_coords_from_world(world_coords: Vec2, tile_size: UVec2) -> Option<UVec2> {
@@ -43,6 +48,24 @@ pub fn get_tile_coords_from_world(world_coords: Vec2, tile_size: UVec2) -> Optio
     }
 }
 
+/// Like [`get_tile_coords_from_world`], but the coords can be outside of the board
+pub fn get_unbounded_tile_coords_from_world(world_coords: Vec2) -> IVec2 {
+    let base_coords = world_coords.div(TILE_SIZE).round();
+    IVec2::new(
+        base_coords.x as i32 - 1,
+        BOARD_SIZE as i32 - 1 - base_coords.y as i32,
+    )
+}
+
+/// Whether any tile of a piece with the given size at the coords is on the board
+pub fn is_piece_over_board(tile_coords: IVec2, tile_size: UVec2) -> bool {
+    let max_i = BOARD_SIZE as i32;
+    tile_coords.x + (tile_size.x as i32) > 0
+        && tile_coords.y + (tile_size.y as i32) > 0
+        && tile_coords.x < max_i
+        && tile_coords.y < max_i
+}
+
 pub fn get_world_coords_from_tile(tile_coords: UVec2) -> Vec2 {
     Vec2::new(
         tile_coords.x as f32 * TILE_SIZE,
@@ -50,6 +73,13 @@ pub fn get_world_coords_from_tile(tile_coords: UVec2) -> Vec2 {
     ) + BOARD_SHIFT.truncate()
 }
 
+/// Center of a piece with the given size placed at the tile coords
+pub fn get_piece_world_coords(tile_coords: UVec2, size: Vec2) -> Vec2 {
+    get_world_coords_from_tile(tile_coords)
+        + Vec2::new(-BOARD_SIZE_PX / 2., BOARD_SIZE_PX / 2.)
+        + Vec2::new(size.x / 2., -size.y / 2.)
+}
+
 fn update_tile_coords(
     cursor_pos: Res<CursorWorldPosition>,
     mut dragged_query: Query<(&mut TileCoords, &Piece, &Transform, &Interactable), With<Dragged>>,
@@ -62,16 +92,18 @@ fn update_tile_coords(
         {
             let tile_size = interactable.bounds.size().div(TILE_SIZE);
             let tile_size = UVec2::new(tile_size.x as u32, tile_size.y as u32);
-            let mut dragged_tile_coords = get_tile_coords_from_world(
-                interactable_t.translation.truncate()
-                    // todo: what's up with this magic offset?
-                    + Vec2::new(
-                        -interactable.bounds.width() / 2. + 5.5 * TILE_SIZE,
-                        interactable.bounds.height() / 2. + 3.5 * TILE_SIZE,
-                    )
-                    + -BOARD_SHIFT.truncate(),
-                tile_size,
-            );
+            let world_coords = interactable_t.translation.truncate()
+                // todo: what's up with this magic offset?
+                + Vec2::new(
+                    -interactable.bounds.width() / 2. + 5.5 * TILE_SIZE,
+                    interactable.bounds.height() / 2. + 3.5 * TILE_SIZE,
+                )
+                + -BOARD_SHIFT.truncate();
+            let mut dragged_tile_coords = get_tile_coords_from_world(world_coords, tile_size);
+
+            let hovered_tile = get_unbounded_tile_coords_from_world(world_coords);
+            coords.hovered_tile =
+                is_piece_over_board(hovered_tile, tile_size).then_some(hovered_tile);
 
             if let Some(dragged_coords) = dragged_tile_coords {
                 let piece = &pieces.pieces[piece.0];
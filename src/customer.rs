@@ -5,9 +5,14 @@ use rand::{thread_rng, Rng};
 use crate::{
     anim::SheetAnimation,
     assets::Sprites,
-    level::LevelEv,
+    level::{get_order_score, LevelEv},
+    localization::Localization,
     order::{Order, OrderEv},
     render::ZIndex,
+    theme::{Theme, ThemeColor},
+    time_scale::{TimeScale, TimeScaled},
+    tween::{get_relative_fade_text_anim, TweenDoneAction},
+    ui::MENU_FONT_SIZE,
     GameState, VIEW_SIZE,
 };
 
@@ -27,6 +32,9 @@ impl Plugin for CustomerPlugin {
     }
 }
 
+/// Above the customer's head
+const REACTION_Y: f32 = 22.;
+
 #[derive(Component)]
 struct Customer {
     order_e: Entity,
@@ -85,6 +93,7 @@ fn spawn_customer(
             speed,
             character_index,
         })
+        .insert(TimeScaled)
         .insert(Name::new("customer"));
     }
 }
@@ -102,6 +111,7 @@ fn wander_around(
         &mut TextureAtlasSprite,
     )>,
     time: Res<Time>,
+    time_scale: Res<TimeScale>,
 ) {
     for (e, mut c, mut c_t, mut sprite) in customer_q.iter_mut() {
         if c_t.translation.x > VIEW_SIZE.x / 2. + 50. {
@@ -114,26 +124,75 @@ fn wander_around(
             }
 
             let going_left = c.target_x < c_t.translation.x;
-            c_t.translation.x +=
-                c.speed * time.delta_seconds() * (if going_left { -1. } else { 1. });
+            c_t.translation.x += c.speed
+                * time.delta_seconds()
+                * time_scale.get_scale()
+                * (if going_left { -1. } else { 1. });
         }
     }
 }
 
 fn on_order_completed(
-    mut customer_q: Query<(&mut Customer, &mut TextureAtlasSprite)>,
+    mut cmd: Commands,
+    mut customer_q: Query<(Entity, &mut Customer, &mut TextureAtlasSprite)>,
     mut order_evr: EventReader<OrderEv>,
+    loc: Localization,
+    theme: Res<Theme>,
 ) {
     for ev in order_evr.iter() {
-        if let OrderEv::Completed(e) = ev {
-            if let Some((mut c, mut sprite)) = customer_q.iter_mut().find(|(c, ..)| c.order_e == *e)
+        if let OrderEv::Completed { order_e, quality } = ev {
+            if let Some((e, mut c, mut sprite)) = customer_q
+                .iter_mut()
+                .find(|(_, c, _)| c.order_e == *order_e)
             {
                 walk_away(&mut c, &mut sprite);
+                spawn_reaction(&mut cmd, e, *quality, &loc, &theme);
             }
         }
     }
 }
 
+fn spawn_reaction(
+    cmd: &mut Commands,
+    customer_e: Entity,
+    quality: f32,
+    loc: &Localization,
+    theme: &Theme,
+) {
+    let (key, color) = if quality >= 1. {
+        ("customer.perfect", ThemeColor::Highlighted)
+    } else if quality >= 0.5 {
+        ("customer.good", ThemeColor::Light)
+    } else {
+        ("customer.poor", ThemeColor::Dark)
+    };
+
+    // the score goes with the reaction, so the player sees what a sloppy soup costs
+    let reaction_e = cmd
+        .spawn_bundle(Text2dBundle {
+            text: Text::from_section(
+                format!("{} +{}", loc.get(key), get_order_score(quality)),
+                TextStyle {
+                    font: loc.get_font(),
+                    font_size: MENU_FONT_SIZE,
+                    color: theme.get(color),
+                },
+            )
+            .with_alignment(TextAlignment::CENTER),
+            transform: Transform::from_xyz(0., REACTION_Y, 0.1),
+            ..default()
+        })
+        .insert(get_relative_fade_text_anim(
+            Color::NONE,
+            1500,
+            Some(TweenDoneAction::DespawnRecursive),
+        ))
+        .insert(Name::new("reaction"))
+        .id();
+
+    cmd.entity(customer_e).add_child(reaction_e);
+}
+
 fn on_level_over(
     mut lvl_evr: EventReader<LevelEv>,
     mut customer_q: Query<(&mut Customer, &mut TextureAtlasSprite)>,
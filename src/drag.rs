@@ -66,7 +66,7 @@ fn on_drag_end(
     mut dragged_q: Query<(&Dragged, &mut Transform, &mut ZIndex)>,
 ) {
     for ev in evr.iter() {
-        if let InteractionEv::DragEnd(drag_data) = ev {
+        if let InteractionEv::DragEnd(drag_data) | InteractionEv::DragCancel(drag_data) = ev {
             if let Ok((dragged, mut t, mut dragged_z)) = dragged_q.get_mut(drag_data.e) {
                 // todo: delay this to prevent z fighting on drag end
                 if let Some(z_index) = dragged.original_z_index {
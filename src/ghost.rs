@@ -0,0 +1,188 @@
+use crate::{
+    board::{Board, PlaceError},
+    coords::{get_piece_world_coords, TileCoords},
+    drag::Dragged,
+    piece::{Piece, PieceFields},
+    render::ZIndex,
+    theme::{Theme, ThemeColor},
+    tile_placement::{Pieces, BOARD_SIZE, TILE_SIZE},
+    GameState,
+};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_prototype_lyon::prelude::*;
+use iyes_loopless::prelude::*;
+
+pub struct GhostPlugin;
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_placement_ghost.run_in_state(GameState::Playing))
+            .add_exit_system(GameState::Playing, despawn_placement_ghost);
+    }
+}
+
+/// Preview of the dragged piece on the board
+#[derive(Component)]
+struct PlacementGhost {
+    piece_index: usize,
+    tile: IVec2,
+}
+
+fn update_placement_ghost(
+    mut cmd: Commands,
+    board: Res<Board>,
+    pieces: Res<Pieces>,
+    theme: Res<Theme>,
+    dragged_q: Query<(&Piece, &TileCoords), With<Dragged>>,
+    ghost_q: Query<(Entity, &PlacementGhost)>,
+) {
+    let hovered = dragged_q
+        .get_single()
+        .ok()
+        .and_then(|(piece, coords)| coords.hovered_tile.map(|tile| (piece.0, tile)));
+    let ghost = ghost_q.get_single().ok();
+
+    if !board.is_changed()
+        && !theme.is_changed()
+        && ghost.map(|(_, g)| (g.piece_index, g.tile)) == hovered
+    {
+        return;
+    }
+
+    if let Some((ghost_e, _)) = ghost {
+        cmd.entity(ghost_e).despawn_recursive();
+    }
+
+    if let Some((piece_index, tile)) = hovered {
+        spawn_placement_ghost(
+            &mut cmd,
+            &theme,
+            &board,
+            &pieces.pieces[piece_index],
+            piece_index,
+            tile,
+        );
+    }
+}
+
+fn spawn_placement_ghost(
+    cmd: &mut Commands,
+    theme: &Theme,
+    board: &Board,
+    piece: &PieceFields,
+    piece_index: usize,
+    tile: IVec2,
+) {
+    let placement = if tile.x < 0 || tile.y < 0 {
+        Err(PlaceError::OutOfBounds)
+    } else {
+        board.predict_clears(tile.x as usize, tile.y as usize, piece.get_fields())
+    };
+
+    let color = theme.get(match placement {
+        Ok(_) => ThemeColor::GhostOk,
+        Err(PlaceError::Taken) => ThemeColor::GhostTaken,
+        Err(PlaceError::OutOfBounds) => ThemeColor::GhostOutOfBounds,
+    });
+
+    // rows, columns and sections the placement would complete
+    let cleared_fields: HashSet<usize> = placement
+        .iter()
+        .flatten()
+        .flat_map(|c| board.get_clear_fields(c))
+        .collect();
+
+    cmd.spawn_bundle(SpatialBundle::default())
+        .insert(ZIndex::PlacementGhost)
+        .insert(PlacementGhost { piece_index, tile })
+        .insert(Name::new("placement_ghost"))
+        .with_children(|b| {
+            for i in cleared_fields {
+                let field_tile = UVec2::new((i % BOARD_SIZE) as u32, (i / BOARD_SIZE) as u32);
+                spawn_ghost_tile(b, field_tile, theme.get(ThemeColor::GhostClear), 0.);
+            }
+
+            for field_tile in get_ghost_tiles(tile, piece) {
+                spawn_ghost_tile(b, field_tile, color, 0.1);
+
+                // invalid placements are marked by a shape too, not just the color
+                if let Err(err) = &placement {
+                    spawn_ghost_cue(b, field_tile, err, theme.get(ThemeColor::Darker));
+                }
+            }
+        });
+}
+
+/// Cross over taken tiles, a bar for the out of bounds placement
+fn spawn_ghost_cue(b: &mut ChildBuilder, tile: UVec2, err: &PlaceError, color: Color) {
+    let h = TILE_SIZE / 2. - 3.;
+    let mut path = PathBuilder::new();
+    match err {
+        PlaceError::Taken => {
+            path.move_to(Vec2::new(-h, -h));
+            path.line_to(Vec2::new(h, h));
+            path.move_to(Vec2::new(-h, h));
+            path.line_to(Vec2::new(h, -h));
+        }
+        PlaceError::OutOfBounds => {
+            path.move_to(Vec2::new(-h, 0.));
+            path.line_to(Vec2::new(h, 0.));
+        }
+    }
+
+    b.spawn_bundle(GeometryBuilder::build_as(
+        &path.build(),
+        DrawMode::Stroke(StrokeMode::new(color, 1.)),
+        Transform::from_translation(
+            get_piece_world_coords(tile, Vec2::splat(TILE_SIZE)).extend(0.2),
+        ),
+    ));
+}
+
+fn spawn_ghost_tile(b: &mut ChildBuilder, tile: UVec2, color: Color, z: f32) {
+    b.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color,
+            custom_size: Some(Vec2::splat(TILE_SIZE)),
+            ..default()
+        },
+        transform: Transform::from_translation(
+            get_piece_world_coords(tile, Vec2::splat(TILE_SIZE)).extend(z),
+        ),
+        ..default()
+    });
+}
+
+/// Board tiles covered by the piece, the fields outside of the board are skipped
+fn get_ghost_tiles(tile: IVec2, piece: &PieceFields) -> Vec<UVec2> {
+    let padded_w = piece.get_padded_width();
+
+    piece
+        .get_fields()
+        .iter()
+        .map(|f| tile + IVec2::new((f % padded_w) as i32, (f / padded_w) as i32))
+        .filter(|t| t.min_element() >= 0 && t.max_element() < BOARD_SIZE as i32)
+        .map(|t| t.as_uvec2())
+        .collect()
+}
+
+fn despawn_placement_ghost(mut cmd: Commands, ghost_q: Query<Entity, With<PlacementGhost>>) {
+    for e in ghost_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(IVec2::new(2, 3) => vec![UVec2::new(2, 3), UVec2::new(2, 4), UVec2::new(3, 4)])]
+    #[test_case(IVec2::new(-1, 0) => vec![UVec2::new(0, 1)])]
+    #[test_case(IVec2::new(8, 8) => vec![UVec2::new(8, 8)])]
+    #[test_case(IVec2::new(9, 0) => Vec::<UVec2>::new())]
+    fn get_ghost_tiles(tile: IVec2) -> Vec<UVec2> {
+        // L piece
+        let piece = PieceFields::new(&[0, 2, 3], 2, BOARD_SIZE);
+        super::get_ghost_tiles(tile, &piece)
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{
+    assets::Sprites,
+    card::{spawn_card, Card, CardKind, StackOrigin, CARD_EXTENDS, CARD_SIZE, MAX_STACK_SIZE},
+    drag::Dragged,
+    highlight::Highligtable,
+    interaction::{Interactable, InteractionGroup},
+    level::{CurrentLevel, LevelEv, Levels},
+    list::ListIndex,
+    localization::Localization,
+    render::{ZIndex, PADDED_VIEW_EXTENDS},
+    save::SaveData,
+    settings::Settings,
+    shop::get_max_cards,
+    theme::{Theme, ThemeColor},
+    tween::{get_relative_fade_text_anim, get_relative_move_by_anim, TweenDoneAction},
+    ui::{spawn_text, MENU_FONT_SIZE},
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use std::collections::VecDeque;
+
+pub struct HandPlugin;
+impl Plugin for HandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CardDropQueue>()
+            .add_exit_system(GameState::Loading, setup)
+            .add_exit_system(GameState::Playing, despawn_hand_full_indicator)
+            .add_system(deal_cards.run_in_state(GameState::Playing))
+            .add_system(
+                update_hand_full_indicator
+                    .run_in_state(GameState::Playing)
+                    .after(deal_cards),
+            )
+            .add_system(on_level_over.run_in_state(GameState::Playing));
+    }
+}
+
+/// Between the board and the first cauldron
+const TRASH_BIN_POS: Vec3 = Vec3::new(-14., -70., 0.);
+/// Right under the card row
+const HAND_FULL_POS: Vec3 = Vec3::new(50., 31., 0.);
+const OVERFLOW_CARD_SCORE: u32 = 10;
+
+/// What happens to the cards dropped into a full hand - they're lost without a policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardOverflow {
+    /// The oldest card makes room for the new one
+    ReplaceOldest,
+    ConvertToScore,
+    /// The cards wait offscreen until there's room
+    Queue,
+}
+
+/// Cards dropped by the clears, dealt to the hand in order
+#[derive(Debug, Default)]
+pub struct CardDropQueue {
+    pub queue: VecDeque<CardKind>,
+}
+
+/// Holds the number of the queued cards shown in the text
+#[derive(Component)]
+struct HandFullIndicator(usize);
+
+fn setup(mut cmd: Commands, sprites: Res<Sprites>, theme: Res<Theme>) {
+    let outline_e = cmd
+        .spawn_bundle(SpriteBundle {
+            texture: sprites.trash_bin_outline.clone(),
+            sprite: Sprite {
+                color: theme.get(ThemeColor::Darker),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., 0.01),
+            ..default()
+        })
+        .insert(Name::new("outline"))
+        .id();
+
+    cmd.spawn_bundle(SpriteBundle {
+        texture: sprites.trash_bin.clone(),
+        transform: Transform::from_translation(TRASH_BIN_POS),
+        ..default()
+    })
+    .insert(ZIndex::Cauldron)
+    .insert(Interactable::new_rectangle(
+        InteractionGroup::Discard,
+        Vec2::splat(8.),
+    ))
+    .insert(Highligtable {
+        sprite_e: Some(outline_e),
+        hightlight_color: ThemeColor::Highlighted,
+        hover_color: ThemeColor::HoveredDrag,
+        normal_color: ThemeColor::Dark,
+        drag_groups: vec![InteractionGroup::Card],
+    })
+    .insert(Name::new("TrashBin"))
+    .add_child(outline_e);
+}
+
+/// Card in the hand the new cards can stack on - `e` is None for the ones dealt this frame
+struct HandSlot {
+    e: Option<Entity>,
+    kind: CardKind,
+    count: u8,
+}
+
+fn deal_cards(
+    mut cmd: Commands,
+    mut drops: ResMut<CardDropQueue>,
+    mut lvl: ResMut<CurrentLevel>,
+    lvls: Res<Levels>,
+    save: Res<SaveData>,
+    sprites: Res<Sprites>,
+    theme: Res<Theme>,
+    loc: Localization,
+    // a card dragged off a stack goes back to it, so it doesn't take a slot
+    card_q: Query<(), (With<Card>, Without<StackOrigin>)>,
+    mut stack_q: Query<(Entity, &CardKind, &mut Card), Without<Dragged>>,
+    placed_card_q: Query<(Entity, &ListIndex<Card>), (With<Card>, Without<Dragged>)>,
+    settings: Res<Settings>,
+) {
+    if drops.queue.is_empty() {
+        return;
+    }
+
+    let overflow = lvls[lvl.level_index].card_overflow;
+    let mut free_count = get_max_cards(&save).saturating_sub(card_q.iter().len());
+    let mut slots: Vec<_> = stack_q
+        .iter()
+        .map(|(e, kind, card)| HandSlot {
+            e: Some(e),
+            kind: *kind,
+            count: card.count,
+        })
+        .collect();
+
+    let mut oldest: Vec<_> = placed_card_q.iter().collect();
+    oldest.sort_by_key(|(_, i)| i.get());
+    let mut oldest = oldest.into_iter().map(|(e, _)| e);
+
+    while let Some(kind) = drops.queue.pop_front() {
+        if stack_onto_slot(&mut slots, kind) {
+            continue;
+        }
+
+        let has_room = free_count > 0
+            || match overflow {
+                Some(CardOverflow::ReplaceOldest) => oldest
+                    .next()
+                    .map(|card_e| {
+                        discard_card(&mut cmd, card_e, settings.reduced_motion);
+                        slots.retain(|s| s.e != Some(card_e));
+                    })
+                    .is_some(),
+                Some(CardOverflow::ConvertToScore) => {
+                    lvl.stats.score += OVERFLOW_CARD_SCORE;
+                    spawn_score_popup(&mut cmd, &loc, &theme);
+                    false
+                }
+                Some(CardOverflow::Queue) => {
+                    drops.queue.push_front(kind);
+                    break;
+                }
+                None => false,
+            };
+
+        if has_room {
+            free_count = free_count.saturating_sub(1);
+            slots.push(HandSlot {
+                e: None,
+                kind,
+                count: 1,
+            });
+        }
+    }
+
+    for slot in slots {
+        match slot.e {
+            Some(e) => {
+                if let Ok((_, _, mut card)) = stack_q.get_mut(e) && card.count != slot.count {
+                    card.count = slot.count;
+                }
+            }
+            None => {
+                spawn_card(&mut cmd, &sprites, &theme, slot.kind, slot.count);
+            }
+        }
+    }
+}
+
+/// Returns false when the card needs a slot of its own
+fn stack_onto_slot(slots: &mut [HandSlot], kind: CardKind) -> bool {
+    // only the ingredients stack
+    if let CardKind::Ingredient(_) = kind
+        && let Some(slot) = slots
+            .iter_mut()
+            .find(|s| s.kind == kind && s.count < MAX_STACK_SIZE)
+    {
+        slot.count += 1;
+        return true;
+    }
+
+    false
+}
+
+/// Fades out where the card would show up
+fn spawn_score_popup(cmd: &mut Commands, loc: &Localization, theme: &Theme) {
+    cmd.spawn_bundle(Text2dBundle {
+        text: Text::from_section(
+            format!("+{OVERFLOW_CARD_SCORE}"),
+            TextStyle {
+                font: loc.get_font(),
+                font_size: MENU_FONT_SIZE,
+                color: theme.get(ThemeColor::Highlighted),
+            },
+        )
+        .with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(PADDED_VIEW_EXTENDS.x - CARD_EXTENDS.x, HAND_FULL_POS.y, 0.),
+        ..default()
+    })
+    .insert(ZIndex::Card)
+    .insert(get_relative_fade_text_anim(
+        Color::NONE,
+        800,
+        Some(TweenDoneAction::DespawnRecursive),
+    ))
+    .insert(Name::new("score_popup"));
+}
+
+fn discard_card(cmd: &mut Commands, card_e: Entity, reduced_motion: bool) {
+    let mut e_cmd = cmd.entity(card_e);
+    e_cmd.insert(get_relative_move_by_anim(
+        Vec3::Y * CARD_SIZE.y * 1.5,
+        350,
+        reduced_motion,
+        Some(TweenDoneAction::DespawnRecursive),
+    ));
+
+    // shifts the rest of the hand
+    e_cmd.remove::<Card>();
+    e_cmd.remove::<ListIndex<Card>>();
+    e_cmd.remove::<Interactable>();
+}
+
+fn update_hand_full_indicator(
+    mut cmd: Commands,
+    drops: Res<CardDropQueue>,
+    lvl: Res<CurrentLevel>,
+    save: Res<SaveData>,
+    loc: Localization,
+    card_q: Query<(), (With<Card>, Without<StackOrigin>)>,
+    mut indicator_q: Query<(Entity, &mut HandFullIndicator, &mut Text)>,
+) {
+    let is_full = !lvl.stopped && card_q.iter().len() >= get_max_cards(&save);
+    let queued_count = drops.queue.len();
+    let get_text = |count: usize| match count {
+        0 => loc.get("card.hand_full"),
+        _ => loc.format("card.hand_full_queued", &[("count", count.to_string())]),
+    };
+
+    match (is_full, indicator_q.get_single_mut()) {
+        (true, Err(_)) => {
+            let txt_e = spawn_text(
+                &mut cmd,
+                &loc,
+                get_text(queued_count),
+                HAND_FULL_POS,
+                TextAlignment::CENTER,
+                ThemeColor::Highlighted,
+            );
+            cmd.entity(txt_e)
+                .insert(HandFullIndicator(queued_count))
+                .insert(ZIndex::Card)
+                .insert(Name::new("hand_full"));
+        }
+        (true, Ok((_, mut indicator, mut txt))) if indicator.0 != queued_count => {
+            indicator.0 = queued_count;
+            txt.sections[0].value = get_text(queued_count);
+        }
+        (false, Ok((e, ..))) => {
+            cmd.entity(e).despawn_recursive();
+        }
+        _ => {}
+    }
+}
+
+fn despawn_hand_full_indicator(
+    mut cmd: Commands,
+    indicator_q: Query<Entity, With<HandFullIndicator>>,
+) {
+    for e in indicator_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+fn on_level_over(mut lvl_evr: EventReader<LevelEv>, mut drops: ResMut<CardDropQueue>) {
+    for ev in lvl_evr.iter() {
+        if let LevelEv::LevelOver { .. } = ev {
+            drops.queue.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Ingredient;
+    use test_case::test_case;
+
+    const PUMPKIN: CardKind = CardKind::Ingredient(Ingredient::Pumpkin);
+    const POTATO: CardKind = CardKind::Ingredient(Ingredient::Potato);
+
+    #[test_case(vec![], PUMPKIN => (false, vec![]))]
+    #[test_case(vec![(PUMPKIN, 1)], PUMPKIN => (true, vec![2]))]
+    #[test_case(vec![(POTATO, 1), (PUMPKIN, 3)], PUMPKIN => (true, vec![1, 4]))]
+    #[test_case(vec![(PUMPKIN, MAX_STACK_SIZE), (PUMPKIN, 2)], PUMPKIN => (true, vec![MAX_STACK_SIZE, 3]) ; "skips full stack")]
+    #[test_case(vec![(PUMPKIN, MAX_STACK_SIZE)], PUMPKIN => (false, vec![MAX_STACK_SIZE]) ; "full stack")]
+    #[test_case(vec![(CardKind::Freeze, 1)], CardKind::Freeze => (false, vec![1]) ; "specials don't stack")]
+    fn stack_onto_slot(slots: Vec<(CardKind, u8)>, kind: CardKind) -> (bool, Vec<u8>) {
+        let mut slots: Vec<_> = slots
+            .into_iter()
+            .map(|(kind, count)| HandSlot {
+                e: None,
+                kind,
+                count,
+            })
+            .collect();
+
+        let stacked = super::stack_onto_slot(&mut slots, kind);
+        (stacked, slots.iter().map(|s| s.count).collect())
+    }
+}
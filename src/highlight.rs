@@ -1,9 +1,11 @@
 use crate::{
     drag::Draggable,
     interaction::{Interactable, InteractionEv, InteractionGroup, InteractionState},
+    theme::{Theme, ThemeColor},
     tween::{get_relative_sprite_color_anim, get_relative_spritesheet_color_anim},
 };
 use bevy::{prelude::*, utils::HashSet};
+use bevy_prototype_lyon::prelude::*;
 
 pub struct HighlightPlugin;
 impl Plugin for HighlightPlugin {
@@ -16,25 +18,35 @@ impl Plugin for HighlightPlugin {
 #[derive(Component)]
 pub struct Highligtable {
     pub sprite_e: Option<Entity>,
-    pub hightlight_color: Color,
-    pub hover_color: Color,
-    pub normal_color: Color,
+    pub hightlight_color: ThemeColor,
+    pub hover_color: ThemeColor,
+    pub normal_color: ThemeColor,
     pub drag_groups: Vec<InteractionGroup>,
 }
 
+/// Arrow above the hovered drop target, so the hover isn't signaled by the color only
+#[derive(Component)]
+struct HoverMarker;
+
 fn highlight_interactable_on_drag(
     mut evr: EventReader<InteractionEv>,
     mut cmd: Commands,
     interactable_q: Query<&Interactable>,
     highlightable_q: Query<(Entity, &Highligtable)>,
     sprite_q: Query<&Sprite>,
+    marker_q: Query<Entity, With<HoverMarker>>,
+    theme: Res<Theme>,
 ) {
     for ev in evr.iter() {
         if let Some((e, start)) = match ev {
             InteractionEv::DragStart(data) => Some((data.e, true)),
-            InteractionEv::DragEnd(data) => Some((data.e, false)),
+            InteractionEv::DragEnd(data) | InteractionEv::DragCancel(data) => Some((data.e, false)),
             _ => None,
         } {
+            if !start {
+                despawn_hover_markers(&mut cmd, &marker_q);
+            }
+
             for (highlightable_e, highlightable) in
                 highlightable_q.iter().filter(|(e2, ..)| *e2 != e)
             {
@@ -45,11 +57,11 @@ fn highlight_interactable_on_drag(
                         highlightable_e,
                         &interactable.group,
                         &sprite_q,
-                        if start {
+                        theme.get(if start {
                             highlightable.hightlight_color
                         } else {
                             highlightable.normal_color
-                        },
+                        }),
                     );
                 }
             }
@@ -64,6 +76,8 @@ fn highlight_draggable_on_hover(
     sprite_q: Query<&Sprite>,
     highlightable_q: Query<(Entity, &Highligtable, Option<&Draggable>)>,
     interactable_q: Query<&Interactable>,
+    marker_q: Query<Entity, With<HoverMarker>>,
+    theme: Res<Theme>,
 ) {
     for ev in evr.iter() {
         if let Some((e, start)) = match ev {
@@ -81,12 +95,20 @@ fn highlight_draggable_on_hover(
                             highlightable_e,
                             &interactable.group,
                             &sprite_q,
-                            if start {
+                            theme.get(if start {
                                 highlightable.hover_color
                             } else {
                                 highlightable.hightlight_color
-                            },
+                            }),
                         );
+
+                        despawn_hover_markers(&mut cmd, &marker_q);
+                        if start
+                            && highlightable.drag_groups.contains(&interactable.group)
+                            && let Ok(target) = interactable_q.get(highlightable_e)
+                        {
+                            spawn_hover_marker(&mut cmd, &theme, highlightable_e, target);
+                        }
                     }
                 } else if draggable.is_some() {
                     // not dragging - hover over a draggable card
@@ -95,11 +117,11 @@ fn highlight_draggable_on_hover(
                         highlightable,
                         highlightable_e,
                         &sprite_q,
-                        if start {
+                        theme.get(if start {
                             highlightable.hover_color
                         } else {
                             highlightable.normal_color
-                        },
+                        }),
                     );
                 }
             }
@@ -138,3 +160,34 @@ fn tween_outline(
             .insert(get_relative_spritesheet_color_anim(color, 220, None));
     }
 }
+
+fn spawn_hover_marker(cmd: &mut Commands, theme: &Theme, target_e: Entity, target: &Interactable) {
+    let marker = shapes::Polygon {
+        points: vec![Vec2::new(-4., 4.), Vec2::new(4., 4.), Vec2::new(0., -2.)],
+        closed: true,
+    };
+    let pos = Vec3::new(
+        (target.bounds.min.x + target.bounds.max.x) / 2.,
+        target.bounds.max.y + 4.,
+        1.,
+    );
+
+    let marker_e = cmd
+        .spawn_bundle(GeometryBuilder::build_as(
+            &marker,
+            DrawMode::Outlined {
+                fill_mode: FillMode::color(theme.get(ThemeColor::HoveredDrag)),
+                outline_mode: StrokeMode::new(theme.get(ThemeColor::Darker), 1.),
+            },
+            Transform::from_translation(pos),
+        ))
+        .insert(HoverMarker)
+        .id();
+    cmd.entity(target_e).add_child(marker_e);
+}
+
+fn despawn_hover_markers(cmd: &mut Commands, marker_q: &Query<Entity, With<HoverMarker>>) {
+    for e in marker_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    assets::Sprites,
+    localization::Localization,
+    order::OrderEv,
+    render::ZIndex,
+    save::SaveData,
+    settings::Settings,
+    theme::ThemeColor,
+    tween::{FadeHierarchyBundle, TweenDoneAction},
+    ui::spawn_text,
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use strum::EnumIter;
+
+pub struct HintPlugin;
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HintEv>()
+            .add_exit_system(GameState::Playing, despawn_hints)
+            .add_system(show_hint.run_in_state(GameState::Playing))
+            .add_system(hide_hint.run_in_state(GameState::Playing));
+    }
+}
+
+const HINT_DURATION_S: f32 = 3.5;
+/// Tooltip sprite is 42px high and the tail points down
+const HINT_OFFSET_Y: f32 = 34.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Hint {
+    /// Ingredient rejected - the cauldron has no room for another kind
+    CauldronFull,
+    /// Ingredient rejected - the soup has as many pieces as the day allows
+    TooManyIngredients,
+    /// Ingredient rejected - the soup has enough of its category, e.g. spices
+    CategoryFull,
+    /// The served soup didn't match the order, or a soup burned
+    WrongSoup,
+    /// A card dropped on a section without any tiles
+    EmptySection,
+}
+
+impl Hint {
+    /// Hints stop showing up once the player has seen them this many times
+    pub fn get_max_count(&self) -> u8 {
+        match self {
+            Hint::CauldronFull => 3,
+            Hint::TooManyIngredients => 2,
+            Hint::CategoryFull => 2,
+            Hint::WrongSoup => 2,
+            Hint::EmptySection => 2,
+        }
+    }
+
+    fn get_key(&self) -> &'static str {
+        match self {
+            Hint::CauldronFull => "hint.cauldron_full",
+            Hint::TooManyIngredients => "hint.too_many_ingredients",
+            Hint::CategoryFull => "hint.category_full",
+            Hint::WrongSoup => "hint.wrong_soup",
+            Hint::EmptySection => "hint.empty_section",
+        }
+    }
+}
+
+/// Player struggled with something - holds the entity the hint points to
+pub struct HintEv {
+    pub hint: Hint,
+    pub target_e: Entity,
+}
+
+#[derive(Component, Deref, DerefMut)]
+struct HintTooltip(Timer);
+
+fn show_hint(
+    mut cmd: Commands,
+    mut hint_evr: EventReader<HintEv>,
+    mut order_evr: EventReader<OrderEv>,
+    mut save: ResMut<SaveData>,
+    settings: Res<Settings>,
+    sprites: Res<Sprites>,
+    loc: Localization,
+    target_q: Query<&GlobalTransform>,
+    hint_q: Query<(), With<HintTooltip>>,
+) {
+    let burned = order_evr.iter().filter_map(|ev| match ev {
+        OrderEv::SoupBurned(cauldron_e) => Some((Hint::WrongSoup, *cauldron_e)),
+        _ => None,
+    });
+    let hints: Vec<_> = hint_evr
+        .iter()
+        .map(|ev| (ev.hint, ev.target_e))
+        .chain(burned)
+        .collect();
+
+    // a single hint at a time
+    if !settings.hints || !hint_q.is_empty() {
+        return;
+    }
+
+    if let Some((hint, target_t)) = hints
+        .into_iter()
+        .filter(|(hint, _)| save.get_hint_count(*hint) < hint.get_max_count())
+        .find_map(|(hint, e)| target_q.get(e).ok().map(|t| (hint, t)))
+    {
+        save.record_hint(hint);
+
+        let txt_e = spawn_text(
+            &mut cmd,
+            &loc,
+            loc.get(hint.get_key()),
+            Vec3::new(0., 3., 0.1),
+            TextAlignment::CENTER,
+            ThemeColor::Dark,
+        );
+
+        cmd.spawn_bundle(SpriteBundle {
+            texture: sprites.hint_tooltip.clone(),
+            transform: Transform::from_translation(
+                target_t.translation() + Vec3::Y * HINT_OFFSET_Y,
+            ),
+            ..default()
+        })
+        .insert(ZIndex::Tooltip)
+        .insert(HintTooltip(Timer::from_seconds(HINT_DURATION_S, false)))
+        .insert(Name::new("hint"))
+        .add_child(txt_e);
+    }
+}
+
+fn hide_hint(mut cmd: Commands, time: Res<Time>, mut hint_q: Query<(Entity, &mut HintTooltip)>) {
+    for (e, mut hint) in hint_q.iter_mut() {
+        if hint.tick(time.delta()).just_finished() {
+            cmd.entity(e).insert_bundle(
+                FadeHierarchyBundle::new(false, 300, Color::NONE)
+                    .with_done_action(TweenDoneAction::DespawnRecursive),
+            );
+        }
+    }
+}
+
+fn despawn_hints(mut cmd: Commands, hint_q: Query<Entity, With<HintTooltip>>) {
+    for e in hint_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
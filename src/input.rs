@@ -1,18 +1,479 @@
-use bevy::prelude::*;
+use crate::{
+    level::{CurrentLevel, LevelEv},
+    nav::{navigate, NavControl},
+    ui::Overlay,
+    GameState,
+};
+use bevy::{input::InputSystem, prelude::*, utils::HashMap};
+use iyes_loopless::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
 
 pub struct GameInputPlugin;
 impl Plugin for GameInputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(handle_kb_input);
+        app.init_resource::<Input<Action>>()
+            .init_resource::<ActionMap>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                update_actions.after(InputSystem).after(navigate),
+            )
+            .add_system(toggle_pause.run_in_state(GameState::Playing))
+            .add_system(
+                restart_day
+                    .run_in_state(GameState::Playing)
+                    .run_in_state(Overlay::None),
+            );
     }
 }
 
-fn handle_kb_input(kb_input: Res<Input<KeyCode>>) {
-    if kb_input.just_pressed(KeyCode::Escape) {
-        // yep, this's lazy
-        // todo: redo
-        web_sys::window().unwrap().location().reload().unwrap();
-        // lvl_evw.send(LevelEv::LevelOver { won: false });
-        // cmd.insert_resource(NextState(GameState::Playing));
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Action {
+    Place,
+    CancelDrag,
+    Pause,
+    Restart,
+    NextTarget,
+    ToggleMute,
+    /// Held while dragging a card stack to move all of it
+    WholeStack,
+    /// Debug builds only, not shown in the controls
+    FastForward,
+}
+
+impl Action {
+    /// String table key of the label
+    pub fn get_key(&self) -> &'static str {
+        match self {
+            Action::Place => "action.place",
+            Action::CancelDrag => "action.cancel",
+            Action::Pause => "action.pause",
+            Action::Restart => "action.restart",
+            Action::NextTarget => "action.next_target",
+            Action::ToggleMute => "action.mute",
+            Action::WholeStack => "action.whole_stack",
+            Action::FastForward => "action.fast_forward",
+        }
+    }
+
+    pub fn is_rebindable(&self) -> bool {
+        *self != Action::FastForward
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+// only keys & buttons from these lists can be bound, so the bindings can be stored by name
+const BINDABLE_KEYS: [KeyCode; 83] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Key0,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Escape,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::Left,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Back,
+    KeyCode::Return,
+    KeyCode::Space,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::LAlt,
+    KeyCode::LControl,
+    KeyCode::LShift,
+    KeyCode::RAlt,
+    KeyCode::RControl,
+    KeyCode::RShift,
+    KeyCode::Tab,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Minus,
+];
+
+const BINDABLE_MOUSE_BUTTONS: [MouseButton; 3] =
+    [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+
+const BINDABLE_GAMEPAD_BUTTONS: [GamepadButtonType; 16] = [
+    GamepadButtonType::South,
+    GamepadButtonType::East,
+    GamepadButtonType::North,
+    GamepadButtonType::West,
+    GamepadButtonType::LeftTrigger,
+    GamepadButtonType::LeftTrigger2,
+    GamepadButtonType::RightTrigger,
+    GamepadButtonType::RightTrigger2,
+    GamepadButtonType::Select,
+    GamepadButtonType::Start,
+    GamepadButtonType::LeftThumb,
+    GamepadButtonType::RightThumb,
+    GamepadButtonType::DPadUp,
+    GamepadButtonType::DPadDown,
+    GamepadButtonType::DPadLeft,
+    GamepadButtonType::DPadRight,
+];
+
+/// Raw input resources the bindings are read from
+pub struct RawInput<'a> {
+    pub kb: &'a Input<KeyCode>,
+    pub mouse: &'a Input<MouseButton>,
+    pub pad: &'a Input<GamepadButton>,
+    pub gamepads: &'a Gamepads,
+}
+
+impl Binding {
+    pub fn is_pressed(&self, input: &RawInput) -> bool {
+        match self {
+            Binding::Key(key) => input.kb.pressed(*key),
+            Binding::Mouse(btn) => input.mouse.pressed(*btn),
+            Binding::Gamepad(btn) => input
+                .gamepads
+                .iter()
+                .any(|gamepad| input.pad.pressed(GamepadButton(*gamepad, *btn))),
+        }
+    }
+
+    pub fn is_just_pressed(&self, input: &RawInput) -> bool {
+        match self {
+            Binding::Key(key) => input.kb.just_pressed(*key),
+            Binding::Mouse(btn) => input.mouse.just_pressed(*btn),
+            Binding::Gamepad(btn) => input
+                .gamepads
+                .iter()
+                .any(|gamepad| input.pad.just_pressed(GamepadButton(*gamepad, *btn))),
+        }
+    }
+
+    /// First bindable key or button pressed this frame
+    pub fn get_just_pressed(input: &RawInput) -> Option<Self> {
+        input
+            .kb
+            .get_just_pressed()
+            .find(|key| BINDABLE_KEYS.contains(*key))
+            .map(|key| Binding::Key(*key))
+            .or_else(|| {
+                input
+                    .mouse
+                    .get_just_pressed()
+                    .find(|btn| BINDABLE_MOUSE_BUTTONS.contains(*btn))
+                    .map(|btn| Binding::Mouse(*btn))
+            })
+            .or_else(|| {
+                input
+                    .pad
+                    .get_just_pressed()
+                    .find(|btn| BINDABLE_GAMEPAD_BUTTONS.contains(&btn.1))
+                    .map(|btn| Binding::Gamepad(btn.1))
+            })
+    }
+
+    pub fn get_label(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Mouse(MouseButton::Left) => "LMB".into(),
+            Binding::Mouse(MouseButton::Right) => "RMB".into(),
+            Binding::Mouse(MouseButton::Middle) => "MMB".into(),
+            Binding::Mouse(MouseButton::Other(btn)) => format!("Mouse {btn}"),
+            Binding::Gamepad(btn) => format!("Pad {btn:?}"),
+        }
+    }
+
+    fn is_same_device(&self, other: &Binding) -> bool {
+        matches!(
+            (self, other),
+            (Binding::Key(_), Binding::Key(_))
+                | (Binding::Mouse(_), Binding::Mouse(_))
+                | (Binding::Gamepad(_), Binding::Gamepad(_))
+        )
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("k-{key:?}"),
+            Binding::Mouse(btn) => format!("m-{btn:?}"),
+            Binding::Gamepad(btn) => format!("g-{btn:?}"),
+        }
+    }
+
+    pub fn parse(val: &str) -> Option<Self> {
+        let (device, name) = val.split_once('-')?;
+
+        match device {
+            "k" => BINDABLE_KEYS
+                .iter()
+                .find(|key| format!("{key:?}") == name)
+                .map(|key| Binding::Key(*key)),
+            "m" => BINDABLE_MOUSE_BUTTONS
+                .iter()
+                .find(|btn| format!("{btn:?}") == name)
+                .map(|btn| Binding::Mouse(*btn)),
+            "g" => BINDABLE_GAMEPAD_BUTTONS
+                .iter()
+                .find(|btn| format!("{btn:?}") == name)
+                .map(|btn| Binding::Gamepad(*btn)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionMap(HashMap<Action, Vec<Binding>>);
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        Self(
+            Action::iter()
+                .map(|action| {
+                    let bindings = match action {
+                        Action::Place => vec![
+                            Binding::Mouse(MouseButton::Left),
+                            Binding::Key(KeyCode::Space),
+                            Binding::Key(KeyCode::Return),
+                            Binding::Gamepad(GamepadButtonType::South),
+                        ],
+                        Action::CancelDrag => vec![
+                            Binding::Mouse(MouseButton::Right),
+                            Binding::Key(KeyCode::Escape),
+                            Binding::Gamepad(GamepadButtonType::East),
+                        ],
+                        Action::Pause => vec![
+                            Binding::Key(KeyCode::P),
+                            Binding::Gamepad(GamepadButtonType::Start),
+                        ],
+                        Action::Restart => vec![
+                            Binding::Key(KeyCode::R),
+                            Binding::Gamepad(GamepadButtonType::Select),
+                        ],
+                        Action::NextTarget => vec![
+                            Binding::Key(KeyCode::Tab),
+                            Binding::Gamepad(GamepadButtonType::RightTrigger),
+                        ],
+                        Action::ToggleMute => vec![Binding::Key(KeyCode::M)],
+                        Action::WholeStack => vec![
+                            Binding::Key(KeyCode::LShift),
+                            Binding::Gamepad(GamepadButtonType::LeftTrigger),
+                        ],
+                        // not a bindable key, so it can't clash with the player's bindings
+                        Action::FastForward => vec![Binding::Key(KeyCode::Grave)],
+                    };
+
+                    (action, bindings)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ActionMap {
+    pub fn get_bindings(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn set_bindings(&mut self, action: Action, bindings: Vec<Binding>) {
+        self.0.insert(action, bindings);
+    }
+
+    /// Replaces the action's binding for the same device & unbinds it from other actions
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        for bindings in self.0.values_mut() {
+            bindings.retain(|b| *b != binding);
+        }
+
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|b| !b.is_same_device(&binding));
+        bindings.push(binding);
+    }
+
+    pub fn is_pressed(&self, action: Action, input: &RawInput) -> bool {
+        self.get_bindings(action)
+            .iter()
+            .any(|b| b.is_pressed(input))
+    }
+
+    pub fn is_just_pressed(&self, action: Action, input: &RawInput) -> bool {
+        self.get_bindings(action)
+            .iter()
+            .any(|b| b.is_just_pressed(input))
+    }
+
+    pub fn get_label(&self, action: Action) -> String {
+        self.get_bindings(action)
+            .iter()
+            .map(|b| b.get_label())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn update_actions(
+    mut actions: ResMut<Input<Action>>,
+    map: Res<ActionMap>,
+    nav: Res<NavControl>,
+    kb_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    pad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    touches: Res<Touches>,
+) {
+    let input = RawInput {
+        kb: &kb_input,
+        mouse: &mouse_input,
+        pad: &pad_input,
+        gamepads: &gamepads,
+    };
+
+    actions.clear();
+
+    for action in Action::iter() {
+        let pressed = if action == Action::Place && nav.active {
+            // cursorless control holds place between the presses
+            nav.is_place_held()
+        } else if action == Action::CancelDrag && touches.iter().count() > 1 {
+            // a second finger aborts the drag
+            true
+        } else {
+            map.is_pressed(action, &input)
+        };
+
+        if pressed {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+}
+
+fn toggle_pause(
+    mut cmd: Commands,
+    actions: Res<Input<Action>>,
+    overlay: Res<CurrentState<Overlay>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        match overlay.0 {
+            Overlay::None => cmd.insert_resource(NextState(Overlay::Pause)),
+            Overlay::Pause => cmd.insert_resource(NextState(Overlay::None)),
+            _ => {}
+        }
+    }
+}
+
+fn restart_day(
+    actions: Res<Input<Action>>,
+    mut lvl: ResMut<CurrentLevel>,
+    mut lvl_evw: EventWriter<LevelEv>,
+) {
+    if actions.just_pressed(Action::Restart) {
+        restart_level(&mut lvl, &mut lvl_evw);
+    }
+}
+
+/// Fails the running day, which restarts it
+pub fn restart_level(lvl: &mut CurrentLevel, lvl_evw: &mut EventWriter<LevelEv>) {
+    if !lvl.stopped {
+        lvl.stopped = true;
+        lvl_evw.send(LevelEv::LevelOver { won: false });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Binding::Key(KeyCode::Space))]
+    #[test_case(Binding::Key(KeyCode::Key1))]
+    #[test_case(Binding::Mouse(MouseButton::Right))]
+    #[test_case(Binding::Gamepad(GamepadButtonType::DPadLeft))]
+    fn binding_roundtrip(binding: Binding) {
+        assert_eq!(Some(binding), Binding::parse(&binding.serialize()));
+    }
+
+    #[test_case("k-Capital" => None)]
+    #[test_case("k-Grave" => None ; "debug key")]
+    #[test_case("m-Other(5)" => None)]
+    #[test_case("x-Space" => None)]
+    #[test_case("Space" => None)]
+    fn parse_binding(val: &str) -> Option<Binding> {
+        Binding::parse(val)
+    }
+
+    #[test]
+    fn rebind() {
+        let mut map = ActionMap::default();
+        map.rebind(Action::Pause, Binding::Key(KeyCode::Space));
+
+        assert_eq!(
+            &[
+                Binding::Gamepad(GamepadButtonType::Start),
+                Binding::Key(KeyCode::Space)
+            ],
+            map.get_bindings(Action::Pause)
+        );
+        assert!(!map
+            .get_bindings(Action::Place)
+            .contains(&Binding::Key(KeyCode::Space)));
     }
 }
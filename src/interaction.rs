@@ -1,6 +1,8 @@
 use crate::{
     drag::{Draggable, Dragged},
+    input::Action,
     mouse::CursorWorldPosition,
+    ui::Overlay,
 };
 use bevy::{
     prelude::*,
@@ -8,6 +10,7 @@ use bevy::{
     utils::{HashMap, HashSet},
 };
 use bevy_tweening::Animator;
+use iyes_loopless::state::CurrentState;
 use strum::{EnumIter, IntoEnumIterator};
 
 pub struct InteractionPlugin;
@@ -28,6 +31,13 @@ pub enum InteractionGroup {
     Grid,
     GridPieces,
     GridSection,
+    Button,
+    /// Order tooltip - freeze card target
+    Order,
+    /// Trash bin for the unwanted cards
+    Discard,
+    /// Cooked soup - served by dropping it on an order
+    Soup,
 }
 
 #[derive(Debug, Component)]
@@ -108,13 +118,16 @@ pub enum InteractionEv {
     HoverEnd(HoverData),
     DragStart(DragData),
     DragEnd(DragData),
+    /// The drag was aborted - the dragged item should go back to its origin
+    DragCancel(DragData),
 }
 
 fn check_interaction(
     mut cmd: Commands,
     mut state: ResMut<InteractionState>,
     cursor: Res<CursorWorldPosition>,
-    mouse_input: Res<Input<MouseButton>>,
+    actions: Res<Input<Action>>,
+    overlay: Res<CurrentState<Overlay>>,
     mut evw: EventWriter<InteractionEv>,
     interactable_q: Query<(
         Entity,
@@ -126,7 +139,10 @@ fn check_interaction(
     )>,
     dragged_q: Query<&Dragged>,
 ) {
-    if let Some(e) = state.dragged_e && !mouse_input.pressed(MouseButton::Left) {
+    if let Some(e) = state.dragged_e && actions.just_pressed(Action::CancelDrag) {
+        state.dragged_e = None;
+        evw.send(InteractionEv::DragCancel(DragData { e, origin: dragged_q.get(e).map_or(Vec2::ZERO, |dragged| dragged.origin) }));
+    } else if let Some(e) = state.dragged_e && !actions.pressed(Action::Place) {
         state.dragged_e = None;
         evw.send(InteractionEv::DragEnd(DragData { e, origin: dragged_q.get(e).map_or(Vec2::ZERO, |dragged| dragged.origin) }));
     }
@@ -144,8 +160,9 @@ fn check_interaction(
                 }
 
                 if state.dragged_e.is_none()
-                    && mouse_input.just_pressed(MouseButton::Left)
+                    && actions.just_pressed(Action::Place)
                     && draggable.is_some()
+                    && overlay.0 == Overlay::None
                 {
                     // no running transform tween
                     if tween.map_or(true, |t| t.tweenable().progress() >= 1.) {
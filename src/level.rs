@@ -3,23 +3,34 @@ use std::ops::{Range, RangeBounds};
 use crate::{
     anim::SheetAnimation,
     assets::{Fonts, Sprites},
-    card::Ingredient,
+    board::{Board, BoardClear},
+    card::{CardDropTable, CardEffect, Ingredient, IngredientMap},
+    cauldron::CauldronSettings,
+    hand::CardOverflow,
     highlight::Highligtable,
+    input::Action,
     interaction::{Interactable, InteractionGroup},
-    order::SpecialOrder,
-    render::{
-        ZIndex, COL_DARK, COL_DARKER, COL_LIGHT, COL_OUTLINE_HIGHLIGHTED, COL_OUTLINE_HOVERED_DRAG,
-    },
-    tile_placement::{Pieces, BOARD_SHIFT, BOARD_SIZE, SECTION_SIZE, TILE_SIZE},
+    localization::Localization,
+    order::{OrderEv, SpecialOrder},
+    recipe::{IngredientCategory, RecipeRule, RecipeRules},
+    render::ZIndex,
+    save::{LevelResult, SaveData},
+    settings::Settings,
+    theme::{Theme, ThemeColor, ThemedText},
+    tile_placement::{BoardEv, Pieces, BOARD_SHIFT, BOARD_SIZE, SECTION_SIZE, TILE_SIZE},
     tools::enum_variant_eq,
+    tutorial::{TutorialStep, TutorialTarget, TutorialTrigger},
     tween::{
         delay_tween, get_fade_out_sprite_anim, get_relative_fade_text_anim,
         get_relative_fade_text_tween, get_relative_move_by_anim, get_relative_move_by_tween,
-        get_relative_move_tween, get_relative_sprite_color_anim, TweenDoneAction,
+        get_relative_move_tween, get_relative_sprite_color_anim, get_relative_sprite_color_tween,
+        TweenDoneAction, REDUCED_MOTION_FADE_MS,
     },
+    ui::{get_star_draw_mode, get_star_shape, Overlay},
     GameState,
 };
 use bevy::{ecs::event::Event, prelude::*};
+use bevy_prototype_lyon::prelude::*;
 use bevy_tweening::{Animator, EaseFunction};
 use iyes_loopless::prelude::*;
 use rand::{distributions::WeightedIndex, thread_rng, Rng};
@@ -29,7 +40,7 @@ impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         let mut levels = vec![
             Level {
-                name: "Soup 101".into(),
+                name: "soup_101".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -43,9 +54,52 @@ impl Plugin for LevelPlugin {
                 total_order_count: 2,
                 special_order: None,
                 pieces_range: Some(0..27),
+                ingredient_map: IngredientMap::default(),
+                // no special cards during the tutorial
+                card_drops: CardDropTable {
+                    max_special_cards: 0,
+                    ..default()
+                },
+                card_overflow: None,
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(1),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::TimeBudget(180.), Objective::NoBurnedSoups],
+                tutorial: vec![
+                    TutorialStep::new(
+                        TutorialTarget::Group(InteractionGroup::Piece),
+                        TutorialTrigger::PlacePiece,
+                        "tutorial.place_piece",
+                        Vec2::new(-91., 70.),
+                    ),
+                    TutorialStep::new(
+                        TutorialTarget::Section(4),
+                        TutorialTrigger::ClearLine,
+                        "tutorial.clear_line",
+                        Vec2::new(-91., 70.),
+                    ),
+                    TutorialStep::new(
+                        TutorialTarget::Group(InteractionGroup::Card),
+                        TutorialTrigger::DropIngredient,
+                        "tutorial.drop_ingredient",
+                        Vec2::new(60., 70.),
+                    ),
+                    TutorialStep::new(
+                        TutorialTarget::Group(InteractionGroup::Fire),
+                        TutorialTrigger::BoostFire,
+                        "tutorial.boost_fire",
+                        Vec2::new(60., 70.),
+                    ),
+                    TutorialStep::new(
+                        TutorialTarget::None,
+                        TutorialTrigger::Click,
+                        "tutorial.serve",
+                        Vec2::new(0., 0.),
+                    ),
+                ],
             },
             Level {
-                name: "Souped Up".into(),
+                name: "souped_up".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -59,9 +113,22 @@ impl Plugin for LevelPlugin {
                 total_order_count: 3,
                 special_order: None,
                 pieces_range: Some(0..27),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: None,
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(2),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::TimeBudget(240.), Objective::NoBurnedSoups],
+                tutorial: vec![TutorialStep::new(
+                    TutorialTarget::Section(0),
+                    TutorialTrigger::ClearSection,
+                    "tutorial.clear_section",
+                    Vec2::new(-91., 70.),
+                )],
             },
             Level {
-                name: "Smells Like Halloween".into(),
+                name: "smells_like_halloween".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -75,9 +142,17 @@ impl Plugin for LevelPlugin {
                 total_order_count: 5,
                 special_order: None,
                 pieces_range: Some(0..27),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::ReplaceOldest),
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(2),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::TimeBudget(360.), Objective::ClearSections(2)],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Cutting Corners".into(),
+                name: "cutting_corners".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -91,9 +166,17 @@ impl Plugin for LevelPlugin {
                 total_order_count: 6,
                 special_order: None,
                 pieces_range: Some(7..19),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::ReplaceOldest),
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(2),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::TimeBudget(420.), Objective::NoSectionClearCards],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Vampire's Best Friend".into(),
+                name: "vampires_best_friend".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -108,9 +191,17 @@ impl Plugin for LevelPlugin {
                 total_order_count: 5,
                 special_order: None,
                 pieces_range: Some(0..27),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::Queue),
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(2),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::NoBurnedSoups, Objective::ClearSections(3)],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Turning Up the Heat".into(),
+                name: "turning_up_the_heat".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -126,9 +217,19 @@ impl Plugin for LevelPlugin {
                 total_order_count: 6,
                 special_order: None,
                 pieces_range: Some(0..27),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::Queue),
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(2),
+                recipe_rules: RecipeRules {
+                    rules: vec![RecipeRule::MaxIngredients(3)],
+                },
+                objectives: vec![Objective::TimeBudget(480.), Objective::NoSectionClearCards],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Fast Food".into(),
+                name: "fast_food".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -145,9 +246,19 @@ impl Plugin for LevelPlugin {
                 total_order_count: 10,
                 special_order: None,
                 pieces_range: Some(0..7),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::ConvertToScore),
+                min_soup_quality: 0.75,
+                cauldrons: CauldronSettings::row(3),
+                recipe_rules: RecipeRules {
+                    rules: vec![RecipeRule::MaxPerCategory(IngredientCategory::Spice, 1)],
+                },
+                objectives: vec![Objective::TimeBudget(300.), Objective::NoBurnedSoups],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "A Recipe for Disaster".into(),
+                name: "a_recipe_for_disaster".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -164,9 +275,20 @@ impl Plugin for LevelPlugin {
                 total_order_count: 7,
                 special_order: None,
                 pieces_range: Some(0..35),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::ConvertToScore),
+                min_soup_quality: 0.5,
+                cauldrons: vec![
+                    CauldronSettings::new(20.).with_capacity(4),
+                    CauldronSettings::new(74.).with_cook_speed(1.25),
+                ],
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::NoBurnedSoups, Objective::ClearSections(4)],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Cutting edge".into(),
+                name: "cutting_edge".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -181,9 +303,17 @@ impl Plugin for LevelPlugin {
                 total_order_count: 5,
                 special_order: None,
                 pieces_range: Some(31..38),
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::Queue),
+                min_soup_quality: 0.75,
+                cauldrons: CauldronSettings::row(3),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![Objective::TimeBudget(420.), Objective::NoSectionClearCards],
+                tutorial: Vec::new(),
             },
             Level {
-                name: "Food Critic".into(),
+                name: "food_critic".into(),
                 allowed_ingredients: vec![
                     Ingredient::Pumpkin,
                     Ingredient::Potato,
@@ -208,6 +338,21 @@ impl Plugin for LevelPlugin {
                     .into(),
                 }),
                 pieces_range: None,
+                ingredient_map: IngredientMap::default(),
+                card_drops: CardDropTable::default(),
+                card_overflow: Some(CardOverflow::ReplaceOldest),
+                min_soup_quality: 1.,
+                cauldrons: CauldronSettings::row(4)
+                    .into_iter()
+                    .map(|c| c.with_cook_speed(0.8))
+                    .collect(),
+                recipe_rules: RecipeRules::default(),
+                objectives: vec![
+                    Objective::TimeBudget(720.),
+                    Objective::NoBurnedSoups,
+                    Objective::ClearSections(5),
+                ],
+                tutorial: Vec::new(),
             },
         ];
 
@@ -215,7 +360,7 @@ impl Plugin for LevelPlugin {
             levels.insert(
                 1,
                 Level {
-                    name: "TEST".into(),
+                    name: "test".into(),
                     allowed_ingredients: vec![
                         Ingredient::Pumpkin,
                         Ingredient::Potato,
@@ -232,6 +377,14 @@ impl Plugin for LevelPlugin {
                     total_order_count: 3,
                     special_order: None,
                     pieces_range: None,
+                    ingredient_map: IngredientMap::default(),
+                    card_drops: CardDropTable::default(),
+                    card_overflow: Some(CardOverflow::Queue),
+                    min_soup_quality: 0.5,
+                    cauldrons: CauldronSettings::row(4),
+                    recipe_rules: RecipeRules::default(),
+                    objectives: Vec::new(),
+                    tutorial: Vec::new(),
                 },
             );
         }
@@ -239,22 +392,39 @@ impl Plugin for LevelPlugin {
         app.add_event::<LevelEv>()
             .insert_resource(Levels(levels))
             .add_startup_system(setup_fade)
+            .add_startup_system(validate_ingredient_maps)
             .add_exit_system(GameState::Loading, setup_app)
             .add_enter_system(GameState::Playing, on_level_in)
             .add_exit_system(GameState::Playing, on_level_out)
-            .add_system(start_day.run_if_resource_exists::<StartDayDelay>())
+            .add_system(
+                start_day
+                    .run_if_resource_exists::<StartDayDelay>()
+                    .run_in_state(Overlay::None),
+            )
+            .add_system(
+                track_level_stats
+                    .run_in_state(GameState::Playing)
+                    .run_in_state(Overlay::None),
+            )
             .add_system(on_level_over)
             .add_system(tween_on_level_ev::<LevelEv>);
     }
 }
 
+pub const MAX_STARS: u8 = 3;
+/// Score for a perfect soup, the worse ones get a part of it
+const ORDER_SCORE: u32 = 100;
+/// Coins for a perfect soup, spent in the shop between the days
+const ORDER_COINS: u32 = 10;
+const TITLE_TEXT_Y: f32 = 2.;
+
 const FAIL_MSGS: [&str; 6] = [
-    "Oh no, you've lost a customer!\nWe can't have that...",
-    "Don't cry over spilled milk\nand try again.",
-    "Come on, use your noodle!",
-    "That's a tough nut to crack.",
-    "Well, aren't you in a pickle",
-    "Don't be such a couch potato.",
+    "fail.lost_customer",
+    "fail.spilled_milk",
+    "fail.noodle",
+    "fail.tough_nut",
+    "fail.pickle",
+    "fail.couch_potato",
 ];
 
 #[derive(PartialEq, Eq)]
@@ -282,11 +452,79 @@ pub struct Level {
     pub next_customer_delay_range_ms: Range<u64>,
     pub special_order: Option<SpecialOrder>,
     pub pieces_range: Option<Range<usize>>,
+    pub ingredient_map: IngredientMap,
+    pub card_drops: CardDropTable,
+    pub card_overflow: Option<CardOverflow>,
+    /// Soups below this quality don't count - 1 accepts only the exact orders
+    pub min_soup_quality: f32,
+    /// 1 to 4 cauldrons
+    pub cauldrons: Vec<CauldronSettings>,
+    /// Limits on top of the cauldrons' capacity
+    pub recipe_rules: RecipeRules,
+    pub objectives: Vec<Objective>,
+    /// Shown until the day has been played
+    pub tutorial: Vec<TutorialStep>,
+}
+
+pub enum Objective {
+    /// Serve all orders within the given time in seconds
+    TimeBudget(f32),
+    NoBurnedSoups,
+    ClearSections(u8),
+    NoSectionClearCards,
+}
+
+impl Objective {
+    pub fn is_met(&self, stats: &LevelStats) -> bool {
+        match self {
+            Objective::TimeBudget(secs) => stats.elapsed_s <= *secs,
+            Objective::NoBurnedSoups => stats.burned_soup_count == 0,
+            Objective::ClearSections(count) => stats.cleared_section_count >= *count,
+            Objective::NoSectionClearCards => stats.section_card_count == 0,
+        }
+    }
+}
+
+pub fn get_order_score(quality: f32) -> u32 {
+    (ORDER_SCORE as f32 * quality).round() as u32
+}
+
+/// Winning a day is worth a star, the other 2 are split between the day's objectives
+pub fn get_star_count(objectives: &[Objective], stats: &LevelStats) -> u8 {
+    if objectives.is_empty() {
+        return MAX_STARS;
+    }
+
+    let met = objectives.iter().filter(|o| o.is_met(stats)).count() as f32;
+    1 + (met / objectives.len() as f32 * (MAX_STARS - 1) as f32).round() as u8
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LevelStats {
+    pub elapsed_s: f32,
+    pub served_order_count: u8,
+    pub burned_soup_count: u8,
+    pub cleared_section_count: u8,
+    pub section_card_count: u8,
+    /// Served soups and the cards converted by the overflow policy
+    pub score: u32,
+    /// Added to the saved coins when the day is won
+    pub coins: u32,
 }
 
 #[derive(Deref, DerefMut)]
 pub struct Levels(Vec<Level>);
 
+pub fn get_day_title(level_index: usize, level: &Level, loc: &Localization) -> String {
+    loc.format(
+        "day.title",
+        &[
+            ("day", (level_index + 1).to_string()),
+            ("name", loc.get(&format!("level.{}", level.name))),
+        ],
+    )
+}
+
 pub struct CurrentLevel {
     pub level_index: usize,
     pub start_timer: Option<Timer>,
@@ -297,6 +535,11 @@ pub struct CurrentLevel {
     pub special_order_index: Option<usize>,
     pub fields_index_offset: usize,
     pub field_weights: WeightedIndex<usize>,
+    pub stats: LevelStats,
+    pub last_day_stars: Option<u8>,
+    pub last_day_score: Option<u32>,
+    /// Limited by the drop table of the day
+    pub special_card_count: usize,
 }
 
 impl CurrentLevel {
@@ -316,9 +559,20 @@ impl CurrentLevel {
             special_order_index: None,
             fields_index_offset,
             field_weights,
+            stats: default(),
+            last_day_stars: None,
+            last_day_score: None,
+            special_card_count: 0,
         }
     }
 
+    pub fn for_level(level_index: usize, retry: bool, lvls: &Levels, pieces: &Pieces) -> Self {
+        let range = lvls[level_index].pieces_range.clone();
+        let dist = pieces.get_distribution(range.clone());
+
+        Self::new(level_index, retry, dist, range.map_or(0, |x| x.start))
+    }
+
     pub fn has_started(&self) -> bool {
         self.start_timer.is_none()
     }
@@ -390,20 +644,26 @@ struct StartDayDelay(Timer);
 #[derive(Component)]
 struct LevelTooltiptext;
 
+#[derive(Component)]
+struct ContinueText;
+
+#[derive(Component)]
+struct LevelTooltipStar(u8);
+
+#[derive(Component)]
+struct LevelTooltipScore;
+
 #[derive(Component, Deref, DerefMut)]
 pub struct InteractableSection(pub usize);
 
 #[derive(Component)]
 struct StartFade;
 
-#[derive(Component)]
-struct Tutorial;
-
-fn setup_fade(mut cmd: Commands) {
+fn setup_fade(mut cmd: Commands, theme: Res<Theme>) {
     cmd.spawn_bundle(SpriteBundle {
         sprite: Sprite {
             custom_size: Some(Vec2::splat(1000.)),
-            color: COL_DARKER,
+            color: theme.get(ThemeColor::Darker),
             ..default()
         },
         transform: Transform::from_xyz(0., 0., 100.),
@@ -412,28 +672,34 @@ fn setup_fade(mut cmd: Commands) {
     .insert(StartFade);
 }
 
+// the maps wrap around, so a mismatch isn't fatal, but the day won't play as designed
+fn validate_ingredient_maps(lvls: Res<Levels>, board: Res<Board>) {
+    for lvl in lvls.iter() {
+        if let Err(err) = lvl.ingredient_map.validate(&board) {
+            error!("Invalid ingredient map of {}: {err}", lvl.name);
+        }
+    }
+}
+
 fn setup_app(
     mut cmd: Commands,
     sprites: Res<Sprites>,
     fonts: Res<Fonts>,
+    theme: Res<Theme>,
     fade_q: Query<Entity, With<StartFade>>,
-    lvl: Res<CurrentLevel>,
+    settings: Res<Settings>,
 ) {
-    if lvl.level_index == 0 {
-        cmd.spawn_bundle(SpriteBundle {
-            texture: sprites.tutorial.clone(),
-            transform: Transform::from_xyz(0., 0., 99.),
-            ..default()
-        })
-        .insert(Tutorial);
-    }
-
     for e in fade_q.iter() {
-        cmd.entity(e).insert(get_relative_sprite_color_anim(
-            Color::NONE,
-            1000,
-            Some(TweenDoneAction::DespawnRecursive),
-        ));
+        if settings.reduced_motion {
+            // no full screen effects
+            cmd.entity(e).despawn_recursive();
+        } else {
+            cmd.entity(e).insert(get_relative_sprite_color_anim(
+                Color::NONE,
+                1000,
+                Some(TweenDoneAction::DespawnRecursive),
+            ));
+        }
     }
 
     for (handle, z_index, name) in [
@@ -529,19 +795,46 @@ fn setup_app(
                 TextStyle {
                     font: fonts.tooltip.clone(),
                     font_size: 16.0,
-                    color: COL_DARK,
+                    color: theme.get(ThemeColor::Dark),
                 },
             )
             .with_alignment(TextAlignment::CENTER),
-            transform: Transform::from_xyz(0., 2., 0.01),
+            transform: Transform::from_xyz(0., TITLE_TEXT_Y, 0.01),
             ..default()
         })
+        .insert(ThemedText(ThemeColor::Dark))
         .insert(LevelTooltiptext);
+    })
+    .with_children(|b| {
+        for i in 0..MAX_STARS {
+            b.spawn_bundle(get_star_shape(
+                Vec3::new((i as f32 - 1.) * 12., 9., 0.01),
+                5.,
+                get_star_draw_mode(None, &theme),
+            ))
+            .insert(LevelTooltipStar(i));
+        }
+
+        b.spawn_bundle(Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: fonts.tooltip.clone(),
+                    font_size: 16.0,
+                    color: theme.get(ThemeColor::Dark),
+                },
+            )
+            .with_alignment(TextAlignment::CENTER_LEFT),
+            transform: Transform::from_xyz(22., 9., 0.01),
+            ..default()
+        })
+        .insert(ThemedText(ThemeColor::Dark))
+        .insert(LevelTooltipScore);
     });
 
     cmd.spawn_bundle(Text2dBundle {
         text: Text::from_section(
-            format!("Click anywhere to start the day..."),
+            "",
             TextStyle {
                 font: fonts.tooltip.clone(),
                 font_size: 16.0,
@@ -555,7 +848,7 @@ fn setup_app(
     .insert(ZIndex::Tooltip)
     .insert(
         EvTween::new(
-            LevelEventTweenType::FadeText(COL_LIGHT),
+            LevelEventTweenType::FadeText(theme.get(ThemeColor::Light)),
             LevelEv::LevelIn,
             LevelEv::LevelStart,
             500,
@@ -563,6 +856,7 @@ fn setup_app(
         .with_delay_in(1500)
         .with_ease_in(EaseFunction::QuadraticInOut),
     )
+    .insert(ContinueText)
     .insert(Name::new("continue_text"));
 
     let corner = Vec2::splat(SECTION_SIZE as f32 / 2. * TILE_SIZE);
@@ -587,19 +881,9 @@ fn setup_app(
         .insert(InteractableSection(i))
         .insert(Highligtable {
             drag_groups: vec![InteractionGroup::Card],
-            normal_color: Color::NONE,
-            hightlight_color: Color::rgba(
-                COL_OUTLINE_HIGHLIGHTED.r(),
-                COL_OUTLINE_HIGHLIGHTED.g(),
-                COL_OUTLINE_HIGHLIGHTED.b(),
-                0.4,
-            ),
-            hover_color: Color::rgba(
-                COL_OUTLINE_HOVERED_DRAG.r(),
-                COL_OUTLINE_HOVERED_DRAG.g(),
-                COL_OUTLINE_HOVERED_DRAG.b(),
-                0.5,
-            ),
+            normal_color: ThemeColor::Transparent,
+            hightlight_color: ThemeColor::SectionHighlighted,
+            hover_color: ThemeColor::SectionHovered,
             sprite_e: None,
         })
         .insert(Name::new("interactable_section"));
@@ -611,18 +895,50 @@ fn on_level_in(
     mut lvl_evw: EventWriter<LevelEv>,
     lvl: Res<CurrentLevel>,
     lvls: Res<Levels>,
-    mut title_txt_q: Query<&mut Text, With<LevelTooltiptext>>,
+    theme: Res<Theme>,
+    loc: Localization,
+    mut title_txt_q: Query<(&mut Text, &mut Transform), With<LevelTooltiptext>>,
+    mut continue_txt_q: Query<&mut Text, (With<ContinueText>, Without<LevelTooltiptext>)>,
+    mut score_txt_q: Query<
+        &mut Text,
+        (
+            With<LevelTooltipScore>,
+            Without<LevelTooltiptext>,
+            Without<ContinueText>,
+        ),
+    >,
+    mut star_q: Query<(&LevelTooltipStar, &mut DrawMode)>,
 ) {
-    title_txt_q.single_mut().sections[0].value = if lvl.retry {
-        FAIL_MSGS[thread_rng().gen_range(0..FAIL_MSGS.len())].into()
+    let (mut title_txt, mut title_t) = title_txt_q.single_mut();
+    title_txt.sections[0].value = if lvl.retry {
+        loc.get(FAIL_MSGS[thread_rng().gen_range(0..FAIL_MSGS.len())])
     } else {
-        format!(
-            "Day {}: {}",
-            lvl.level_index + 1,
-            lvls[lvl.level_index].name
-        )
+        get_day_title(lvl.level_index, &lvls[lvl.level_index], &loc)
     };
 
+    // the language might have changed since the last day
+    let mut continue_txt = continue_txt_q.single_mut();
+    continue_txt.sections[0].value = loc.get("day.continue");
+    // score of the previous day is shown next to its stars
+    let mut score_txt = score_txt_q.single_mut();
+    score_txt.sections[0].value = lvl.last_day_score.map_or(String::new(), |score| {
+        loc.format("day.score", &[("score", score.to_string())])
+    });
+    for txt in [&mut title_txt, &mut continue_txt, &mut score_txt] {
+        txt.sections[0].style.font = loc.get_font();
+    }
+
+    // stars of the previous day are shown above the title
+    title_t.translation.y = if lvl.last_day_stars.is_some() {
+        TITLE_TEXT_Y - 7.
+    } else {
+        TITLE_TEXT_Y
+    };
+
+    for (star, mut draw_mode) in star_q.iter_mut() {
+        *draw_mode = get_star_draw_mode(lvl.last_day_stars.map(|stars| star.0 < stars), &theme);
+    }
+
     lvl_evw.send(LevelEv::LevelIn);
     cmd.insert_resource(StartDayDelay(Timer::from_seconds(2.15, false)));
 }
@@ -633,26 +949,53 @@ fn start_day(
     mut delay: ResMut<StartDayDelay>,
     mut lvl: ResMut<CurrentLevel>,
     time: Res<Time>,
-    mouse_input: Res<Input<MouseButton>>,
-    tutorial_q: Query<Entity, With<Tutorial>>,
+    actions: Res<Input<Action>>,
 ) {
     delay.tick(time.delta());
 
-    if delay.finished() {
-        if mouse_input.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
-            if tutorial_q.iter().len() > 0 {
-                for e in tutorial_q.iter() {
-                    cmd.entity(e).insert(get_relative_sprite_color_anim(
-                        Color::NONE,
-                        1000,
-                        Some(TweenDoneAction::DespawnRecursive),
-                    ));
-                }
-            } else {
-                cmd.remove_resource::<StartDayDelay>();
-                lvl_evw.send(LevelEv::LevelStart);
-                lvl.stopped = false;
+    if delay.finished() && actions.any_just_pressed([Action::Place, Action::CancelDrag]) {
+        cmd.remove_resource::<StartDayDelay>();
+        lvl_evw.send(LevelEv::LevelStart);
+        lvl.stopped = false;
+    }
+}
+
+fn track_level_stats(
+    mut lvl: ResMut<CurrentLevel>,
+    mut order_evr: EventReader<OrderEv>,
+    mut board_evr: EventReader<BoardEv>,
+    mut card_evr: EventReader<CardEffect>,
+    time: Res<Time>,
+) {
+    if !lvl.stopped {
+        // real time, so the fast-forward & slow-mo don't change the results
+        lvl.stats.elapsed_s += time.delta_seconds();
+    }
+
+    for ev in order_evr.iter() {
+        match ev {
+            OrderEv::Completed { quality, .. } => {
+                lvl.stats.served_order_count += 1;
+                lvl.stats.score += get_order_score(*quality);
+                lvl.stats.coins += (ORDER_COINS as f32 * quality).round() as u32;
             }
+            OrderEv::SoupBurned(_) => lvl.stats.burned_soup_count += 1,
+        }
+    }
+
+    for ev in board_evr.iter() {
+        if let BoardEv::Cleared(BoardClear::Section {
+            used_special: false,
+            ..
+        }) = ev
+        {
+            lvl.stats.cleared_section_count += 1;
+        }
+    }
+
+    for ev in card_evr.iter() {
+        if let CardEffect::ClearSection { .. } = ev {
+            lvl.stats.section_card_count += 1;
         }
     }
 }
@@ -660,6 +1003,7 @@ fn start_day(
 fn on_level_over(
     mut cmd: Commands,
     mut lvl_evr: EventReader<LevelEv>,
+    mut save: ResMut<SaveData>,
     lvl: Res<CurrentLevel>,
     lvls: Res<Levels>,
     pieces: Res<Pieces>,
@@ -667,30 +1011,39 @@ fn on_level_over(
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelOver { won } = ev {
             let mut lvl_i = lvl.level_index;
+            let stars = won.then(|| get_star_count(&lvls[lvl.level_index].objectives, &lvl.stats));
+
+            save.record_result(
+                lvl.level_index,
+                LevelResult {
+                    best_time_s: won.then_some(lvl.stats.elapsed_s),
+                    best_orders_served: lvl.stats.served_order_count,
+                    best_stars: stars.unwrap_or(0),
+                    best_score: lvl.stats.score,
+                },
+            );
 
             if *won {
+                save.unlock((lvl.level_index + 1).min(lvls.len() - 1));
+                save.coins += lvl.stats.coins;
+
                 if lvl.level_index >= lvls.len() - 1 {
                     // restart current lvl if the player wants to go again
                     lvl_i = 0;
                     cmd.insert_resource(NextState::<GameState>(GameState::Won));
                 } else {
-                    // next lvl
+                    // next lvl, after a visit to the shop
                     lvl_i = lvl.level_index + 1;
-                    cmd.insert_resource(NextState::<GameState>(GameState::Playing));
+                    cmd.insert_resource(NextState::<GameState>(GameState::Shop));
                 }
             } else {
                 cmd.insert_resource(NextState::<GameState>(GameState::Playing));
             }
 
-            let range = lvls[lvl_i].pieces_range.clone();
-            let dist = pieces.get_distribution(range.clone());
-
-            cmd.insert_resource(CurrentLevel::new(
-                lvl_i,
-                !won,
-                dist,
-                range.map_or(0, |x| x.start),
-            ));
+            let mut next_lvl = CurrentLevel::for_level(lvl_i, !won, &lvls, &pieces);
+            next_lvl.last_day_stars = stars;
+            next_lvl.last_day_score = won.then_some(lvl.stats.score);
+            cmd.insert_resource(next_lvl);
 
             break;
         }
@@ -705,6 +1058,8 @@ pub fn tween_on_level_ev<T: Event + Eq>(
     mut cmd: Commands,
     mut lvl_evr: EventReader<T>,
     tween_q: Query<(Entity, &EvTween<T>)>,
+    mut sprite_q: Query<&mut Sprite>,
+    settings: Res<Settings>,
 ) {
     for ev in lvl_evr.iter() {
         for (tween_e, tween) in tween_q.iter() {
@@ -718,16 +1073,41 @@ pub fn tween_on_level_ev<T: Event + Eq>(
                     (-1., tween.duration_out, tween.delay_out, tween.ease_out)
                 };
 
+                // the slide is instant with reduced motion, so the sprite fades in instead
+                if is_in
+                    && settings.reduced_motion
+                    && !matches!(tween.tween_type, LevelEventTweenType::FadeText(_))
+                    && let Ok(mut sprite) = sprite_q.get_mut(tween_e)
+                {
+                    sprite.color = Color::NONE;
+                    tween_e_cmd.insert(Animator::new(delay_tween(
+                        get_relative_sprite_color_tween(Color::WHITE, REDUCED_MOTION_FADE_MS, None),
+                        delay,
+                    )));
+                }
+
                 match tween.tween_type {
                     LevelEventTweenType::MoveByX(x) => {
                         tween_e_cmd.insert(Animator::new(delay_tween(
-                            get_relative_move_by_tween(Vec3::X * x * sign, duration, ease, None),
+                            get_relative_move_by_tween(
+                                Vec3::X * x * sign,
+                                duration,
+                                settings.reduced_motion,
+                                ease,
+                                None,
+                            ),
                             delay,
                         )));
                     }
                     LevelEventTweenType::MoveByY(y) => {
                         tween_e_cmd.insert(Animator::new(delay_tween(
-                            get_relative_move_by_tween(Vec3::Y * y * sign, duration, ease, None),
+                            get_relative_move_by_tween(
+                                Vec3::Y * y * sign,
+                                duration,
+                                settings.reduced_motion,
+                                ease,
+                                None,
+                            ),
                             delay,
                         )));
                     }
@@ -743,3 +1123,38 @@ pub fn tween_on_level_ev<T: Event + Eq>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn get_stats(elapsed_s: f32, burned_soup_count: u8, cleared_section_count: u8) -> LevelStats {
+        LevelStats {
+            elapsed_s,
+            burned_soup_count,
+            cleared_section_count,
+            ..default()
+        }
+    }
+
+    #[test_case(vec![], get_stats(999., 5, 0) => 3)]
+    #[test_case(vec![Objective::NoBurnedSoups], get_stats(0., 0, 0) => 3)]
+    #[test_case(vec![Objective::NoBurnedSoups], get_stats(0., 1, 0) => 1)]
+    #[test_case(vec![Objective::TimeBudget(60.), Objective::NoBurnedSoups], get_stats(50., 1, 0) => 2)]
+    #[test_case(vec![Objective::TimeBudget(60.), Objective::NoBurnedSoups], get_stats(70., 1, 0) => 1)]
+    #[test_case(vec![Objective::TimeBudget(60.), Objective::NoBurnedSoups, Objective::ClearSections(2)], get_stats(50., 1, 1) => 2)]
+    #[test_case(vec![Objective::TimeBudget(60.), Objective::NoBurnedSoups, Objective::ClearSections(2)], get_stats(50., 1, 2) => 2)]
+    #[test_case(vec![Objective::TimeBudget(60.), Objective::NoBurnedSoups, Objective::ClearSections(2)], get_stats(50., 0, 2) => 3)]
+    fn get_star_count(objectives: Vec<Objective>, stats: LevelStats) -> u8 {
+        super::get_star_count(&objectives, &stats)
+    }
+
+    #[test_case(1. => 100)]
+    #[test_case(0.75 => 75)]
+    #[test_case(0.333 => 33)]
+    #[test_case(0. => 0)]
+    fn get_order_score(quality: f32) -> u32 {
+        super::get_order_score(quality)
+    }
+}
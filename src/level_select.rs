@@ -0,0 +1,205 @@
+use crate::{
+    level::{get_day_title, CurrentLevel, Levels, MAX_STARS},
+    localization::Localization,
+    render::ZIndex,
+    save::SaveData,
+    settings::{Language, Settings},
+    theme::{Theme, ThemeColor},
+    tile_placement::Pieces,
+    ui::{
+        get_star_draw_mode, get_star_shape, spawn_button, spawn_panel, spawn_text, ButtonClickEv,
+        Overlay, MENU_LINE_HEIGHT,
+    },
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+pub struct LevelSelectPlugin;
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Menu, spawn_level_select)
+            .add_exit_system(GameState::Menu, despawn_level_select)
+            .add_system(
+                select_level
+                    .run_in_state(GameState::Menu)
+                    .run_in_state(Overlay::None),
+            )
+            .add_system(refresh_on_language_change.run_in_state(GameState::Menu));
+    }
+}
+
+const LIST_X: f32 = -130.;
+const STATS_X: f32 = 130.;
+const STARS_X: f32 = 15.;
+const LIST_TOP_Y: f32 = 58.;
+
+#[derive(Component)]
+struct LevelSelect;
+
+#[derive(Component)]
+struct LevelButton(usize);
+
+/// Opens the overlay screen
+#[derive(Component)]
+struct OverlayButton(Overlay);
+
+fn spawn_level_select(
+    mut cmd: Commands,
+    loc: Localization,
+    theme: Res<Theme>,
+    lvls: Res<Levels>,
+    save: Res<SaveData>,
+) {
+    let mut children = vec![spawn_text(
+        &mut cmd,
+        &loc,
+        loc.get("menu.pick_day"),
+        Vec3::new(0., LIST_TOP_Y + MENU_LINE_HEIGHT * 1.5, 0.1),
+        TextAlignment::CENTER,
+        ThemeColor::Light,
+    )];
+
+    for (i, lvl) in lvls.iter().enumerate() {
+        let y = LIST_TOP_Y - i as f32 * MENU_LINE_HEIGHT;
+        let unlocked = save.is_unlocked(i);
+
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            if unlocked {
+                get_day_title(i, lvl, &loc)
+            } else {
+                loc.format("day.locked", &[("day", (i + 1).to_string())])
+            },
+            Vec3::new(LIST_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            STARS_X - LIST_X - 5.,
+            if unlocked {
+                ThemeColor::Light
+            } else {
+                ThemeColor::Dark
+            },
+            unlocked,
+        );
+        cmd.entity(btn_e).insert(LevelButton(i));
+        children.push(btn_e);
+
+        if let Some(res) = save.results.get(&i) {
+            children.push(spawn_text(
+                &mut cmd,
+                &loc,
+                format!(
+                    "{}  {}/{}  {}",
+                    res.best_time_s.map_or("-:--".into(), format_time),
+                    res.best_orders_served,
+                    lvl.total_order_count,
+                    loc.format("day.score", &[("score", res.best_score.to_string())])
+                ),
+                Vec3::new(STATS_X, y, 0.1),
+                TextAlignment::CENTER_RIGHT,
+                ThemeColor::Light,
+            ));
+
+            for star_i in 0..MAX_STARS {
+                children.push(
+                    cmd.spawn_bundle(get_star_shape(
+                        Vec3::new(STARS_X + star_i as f32 * 9., y + 1., 0.1),
+                        4.,
+                        get_star_draw_mode(Some(star_i < res.best_stars), &theme),
+                    ))
+                    .id(),
+                );
+            }
+        }
+    }
+
+    for (x, overlay, text) in [
+        (-40., Overlay::Controls, "menu.controls"),
+        (40., Overlay::Settings, "menu.settings"),
+    ] {
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            loc.get(text),
+            Vec3::new(
+                x,
+                LIST_TOP_Y - (lvls.len() as f32 + 0.5) * MENU_LINE_HEIGHT,
+                0.1,
+            ),
+            TextAlignment::CENTER,
+            60.,
+            ThemeColor::Light,
+            true,
+        );
+        cmd.entity(btn_e).insert(OverlayButton(overlay));
+        children.push(btn_e);
+    }
+
+    let panel_e = spawn_panel(
+        &mut cmd,
+        &theme,
+        Vec2::new(290., 170.),
+        ZIndex::Menu,
+        "level_select",
+        &children,
+    );
+    cmd.entity(panel_e).insert(LevelSelect);
+}
+
+fn despawn_level_select(mut cmd: Commands, select_q: Query<Entity, With<LevelSelect>>) {
+    for e in select_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+fn select_level(
+    mut cmd: Commands,
+    mut click_evr: EventReader<ButtonClickEv>,
+    button_q: Query<&LevelButton>,
+    overlay_btn_q: Query<&OverlayButton>,
+    lvls: Res<Levels>,
+    pieces: Res<Pieces>,
+) {
+    for ev in click_evr.iter() {
+        if let Ok(btn) = button_q.get(ev.0) {
+            cmd.insert_resource(CurrentLevel::for_level(btn.0, false, &lvls, &pieces));
+            cmd.insert_resource(NextState(GameState::Playing));
+            break;
+        } else if let Ok(btn) = overlay_btn_q.get(ev.0) {
+            cmd.insert_resource(NextState(btn.0.clone()));
+            break;
+        }
+    }
+}
+
+// the settings overlay is open above the list, so it's just spawned again
+fn refresh_on_language_change(
+    mut cmd: Commands,
+    settings: Res<Settings>,
+    mut language: Local<Option<Language>>,
+) {
+    if language
+        .replace(settings.language)
+        .map_or(false, |l| l != settings.language)
+    {
+        cmd.insert_resource(NextState(GameState::Menu));
+    }
+}
+
+fn format_time(secs: f32) -> String {
+    let secs = secs.round() as u32;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    #[test_case(0. => "0:00")]
+    #[test_case(9.6 => "0:10")]
+    #[test_case(125.2 => "2:05")]
+    fn format_time(secs: f32) -> String {
+        super::format_time(secs)
+    }
+}
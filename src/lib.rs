@@ -12,31 +12,49 @@
 
 mod anim;
 mod assets;
+mod audio;
 mod board;
 mod card;
 mod cauldron;
+mod controls;
 mod coords;
 mod customer;
 mod drag;
+mod ghost;
+mod hand;
 mod highlight;
+mod hint;
 mod interaction;
 mod level;
+mod level_select;
 mod list;
+mod localization;
 mod mouse;
 mod mover;
+mod music;
+mod nav;
 mod order;
+mod pause;
 mod piece;
 mod progress;
+mod recipe;
 mod render;
 mod save;
+mod settings;
+mod shop;
+mod theme;
 mod tile_placement;
+mod time_scale;
 mod tools;
+mod tutorial;
 mod tween;
+mod ui;
 mod win;
 
 use crate::tile_placement::TilePlacementPlugin;
 use anim::AnimationPlugin;
 use assets::AssetsPlugin;
+use audio::GameAudioPlugin;
 use bevy::app::App;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
@@ -45,16 +63,25 @@ use bevy_prototype_lyon::prelude::ShapePlugin;
 use bevy_tweening::TweeningPlugin;
 use card::CardPlugin;
 use cauldron::CauldronPlugin;
+use controls::ControlsPlugin;
 use coords::CoordsPlugin;
 use customer::CustomerPlugin;
 use drag::DragPlugin;
+use ghost::GhostPlugin;
+use hand::HandPlugin;
 use highlight::HighlightPlugin;
+use hint::HintPlugin;
 use input::GameInputPlugin;
 use interaction::InteractionPlugin;
 use level::LevelPlugin;
+use level_select::LevelSelectPlugin;
+use localization::LocalizationPlugin;
 use mouse::MousePlugin;
 use mover::MoverPlugin;
+use music::MusicPlugin;
+use nav::NavPlugin;
 use order::OrderPlugin;
+use pause::PausePlugin;
 use progress::ProgressPlugin;
 use render::RenderPlugin;
 mod input;
@@ -70,13 +97,21 @@ enum GameState {
     // During this State the actual game logic is executed
     Playing,
     Won,
+    // Cauldron upgrades are bought between the days
+    Shop,
     // Here the menu is drawn and waiting for player interaction
     Menu,
 }
 
 pub use render::VIEW_SIZE;
 use save::SavePlugin;
+use settings::SettingsPlugin;
+use shop::ShopPlugin;
+use theme::ThemePlugin;
+use time_scale::TimeScalePlugin;
+use tutorial::TutorialPlugin;
 use tween::GameTweenPlugin;
+use ui::UiPlugin;
 use win::WinPlugin;
 
 pub struct GamePlugin;
@@ -86,6 +121,7 @@ impl Plugin for GamePlugin {
         app.add_plugin(AssetsPlugin)
             .add_plugin(PixelCameraPlugin)
             .add_plugin(RenderPlugin)
+            .add_plugin(ThemePlugin)
             .add_plugin(InteractionPlugin)
             .add_plugin(DragPlugin)
             .add_plugin(MoverPlugin)
@@ -93,18 +129,33 @@ impl Plugin for GamePlugin {
             .add_plugin(HighlightPlugin)
             .add_plugin(ShapePlugin)
             .add_plugin(TilePlacementPlugin)
+            .add_plugin(GhostPlugin)
             .add_plugin(LevelPlugin)
+            .add_plugin(LevelSelectPlugin)
+            .add_plugin(LocalizationPlugin)
+            .add_plugin(UiPlugin)
+            .add_plugin(PausePlugin)
+            .add_plugin(ControlsPlugin)
+            .add_plugin(SettingsPlugin)
             .add_plugin(WinPlugin)
             .add_plugin(CardPlugin)
+            .add_plugin(HandPlugin)
             .add_plugin(CauldronPlugin)
             .add_plugin(CustomerPlugin)
             .add_plugin(ProgressPlugin)
             .add_plugin(CoordsPlugin)
             .add_plugin(MousePlugin)
+            .add_plugin(NavPlugin)
             .add_plugin(OrderPlugin)
             .add_plugin(TweeningPlugin)
             .add_plugin(GameTweenPlugin)
+            .add_plugin(TimeScalePlugin)
             .add_plugin(GameInputPlugin)
+            .add_plugin(GameAudioPlugin)
+            .add_plugin(MusicPlugin)
+            .add_plugin(TutorialPlugin)
+            .add_plugin(HintPlugin)
+            .add_plugin(ShopPlugin)
             .add_plugin(SavePlugin);
 
         if cfg!(debug_assertions) {
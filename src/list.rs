@@ -1,3 +1,5 @@
+use crate::settings::Settings;
+use crate::time_scale::TimeScale;
 use crate::tween::{get_move_anim, get_relative_move_anim};
 use bevy::prelude::*;
 use bevy_tweening::EaseFunction;
@@ -68,10 +70,15 @@ impl<T> ListIndex<T> {
     pub fn new(index: usize) -> Self {
         Self(index, PhantomData::default())
     }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
 }
 
+/// Items spawned with an index already keep their place, e.g. the rest of a card stack
 fn enqueue_place_items<T: Component>(
-    mut new_item_q: Query<Entity, Added<T>>,
+    mut new_item_q: Query<Entity, (Added<T>, Without<ListIndex<T>>)>,
     mut queue: ResMut<ListTweenQueue<T>>,
 ) {
     for e in new_item_q.iter_mut() {
@@ -88,9 +95,14 @@ fn enqueue_shift_items<T: Component>(
     }
 }
 
-fn run_queue_timer<T: Component>(mut queue: ResMut<ListTweenQueue<T>>, time: Res<Time>) {
+fn run_queue_timer<T: Component>(
+    mut queue: ResMut<ListTweenQueue<T>>,
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+) {
     if let Some(timer) = &mut queue.timer {
-        timer.tick(time.delta());
+        // the list tweens are scaled too
+        timer.tick(time_scale.scale_delta(time.delta()));
 
         if timer.just_finished() {
             queue.timer = None;
@@ -100,9 +112,10 @@ fn run_queue_timer<T: Component>(mut queue: ResMut<ListTweenQueue<T>>, time: Res
 
 fn shift_items<T: Component>(
     mut cmd: Commands,
-    mut index_q: Query<(&mut ListIndex<T>, &mut Transform, Entity)>,
+    mut index_q: Query<(&mut ListIndex<T>, &mut Transform, Entity), With<T>>,
     mut queue: ResMut<ListTweenQueue<T>>,
     opts: Res<ListOptions<T>>,
+    settings: Res<Settings>,
 ) {
     if queue.timer.is_none() {
         if queue.shift {
@@ -133,6 +146,7 @@ fn shift_items<T: Component>(
                         cmd.entity(*c_e).insert(get_relative_move_anim(
                             target_pos,
                             opts.options.shift_duration_ms,
+                            settings.reduced_motion,
                             None,
                         ));
                         c_index.0 = i;
@@ -147,8 +161,10 @@ fn shift_items<T: Component>(
 fn place_items<T: Component>(
     mut cmd: Commands,
     mut item_q: Query<(Entity, &mut Sprite, &mut Transform), With<T>>,
+    indexed_q: Query<(), (With<T>, With<ListIndex<T>>)>,
     mut queue: ResMut<ListTweenQueue<T>>,
     opts: Res<ListOptions<T>>,
+    settings: Res<Settings>,
 ) {
     // prioritize shifting over placing items
     if queue.timer.is_none() && !queue.shift && queue.place_queue.len() > 0 {
@@ -157,7 +173,8 @@ fn place_items<T: Component>(
             false,
         ));
 
-        let mut item_i = item_q.iter().len() - queue.place_queue.len();
+        // items dragged off a stack aren't in the list
+        let mut item_i = indexed_q.iter().len();
 
         for e in queue.place_queue.drain(..) {
             if let Ok((c_e, mut c_sprite, mut item_t)) = item_q.get_mut(e) {
@@ -184,6 +201,7 @@ fn place_items<T: Component>(
                         start_pos,
                         target_pos,
                         opts.options.place_duration_ms,
+                        settings.reduced_motion,
                         EaseFunction::QuadraticOut,
                         None,
                     ));
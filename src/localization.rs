@@ -0,0 +1,142 @@
+use crate::{
+    assets::{Fonts, StringTables},
+    settings::{Language, Settings},
+    GameState,
+};
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    ecs::system::SystemParam,
+    prelude::*,
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+use iyes_loopless::prelude::*;
+
+pub struct LocalizationPlugin;
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<StringTable>()
+            .init_asset_loader::<StringTableLoader>()
+            .add_system(update_window_title.run_not_in_state(GameState::Loading));
+    }
+}
+
+/// `key = value` pairs loaded from the `.lang` files
+#[derive(Debug, Default, TypeUuid)]
+#[uuid = "5f1d4b0e-3c55-4c7a-9a8e-2f4f0c6d7b91"]
+pub struct StringTable {
+    strings: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let table = parse_string_table(std::str::from_utf8(bytes)?);
+            load_context.set_default_asset(LoadedAsset::new(table));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lang"]
+    }
+}
+
+/// Strings of the picked language, English is used for the missing keys
+#[derive(SystemParam)]
+pub struct Localization<'w, 's> {
+    pub settings: Res<'w, Settings>,
+    tables: Res<'w, StringTables>,
+    table_assets: Res<'w, Assets<StringTable>>,
+    fonts: Res<'w, Fonts>,
+    #[system_param(ignore)]
+    _marker: std::marker::PhantomData<&'s ()>,
+}
+
+impl<'w, 's> Localization<'w, 's> {
+    pub fn get(&self, key: &str) -> String {
+        [self.settings.language, Language::English]
+            .into_iter()
+            .find_map(|lang| self.get_table(lang).and_then(|t| t.strings.get(key)))
+            .cloned()
+            // show the key, so the missing strings are easy to spot
+            .unwrap_or_else(|| key.into())
+    }
+
+    /// Replaces the `{name}` placeholders
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        format_string(&self.get(key), args)
+    }
+
+    /// Font of the language, the pixel fonts lack most of the non-english glyphs
+    pub fn get_font(&self) -> Handle<Font> {
+        self.fonts.get(&self.get("font"))
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.settings.is_changed()
+    }
+
+    fn get_table(&self, language: Language) -> Option<&StringTable> {
+        self.table_assets.get(self.tables.get_table(language))
+    }
+}
+
+fn update_window_title(
+    loc: Localization,
+    mut windows: ResMut<Windows>,
+    mut title_set: Local<bool>,
+) {
+    if (loc.is_changed() || !*title_set)
+        && let Some(window) = windows.get_primary_mut()
+    {
+        window.set_title(loc.get("window.title"));
+        *title_set = true;
+    }
+}
+
+// `#` starts a comment, `\n` is a line break
+fn parse_string_table(val: &str) -> StringTable {
+    let strings = val
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(key, val)| (key.trim().into(), val.trim().replace("\\n", "\n")))
+        .collect();
+
+    StringTable { strings }
+}
+
+fn format_string(template: &str, args: &[(&str, String)]) -> String {
+    args.iter().fold(template.into(), |txt, (name, val)| {
+        txt.replace(&format!("{{{name}}}"), val)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("a = b" => vec![("a".into(), "b".into())])]
+    #[test_case("# comment\n\nday= Den {day} " => vec![("day".into(), "Den {day}".into())])]
+    #[test_case("msg = Oh no!\\nAgain = ok" => vec![("msg".into(), "Oh no!\nAgain = ok".into())])]
+    #[test_case("garbage" => Vec::<(String, String)>::new())]
+    fn parse_string_table(val: &str) -> Vec<(String, String)> {
+        super::parse_string_table(val).strings.into_iter().collect()
+    }
+
+    #[test_case("Day {day}: {name}", &[("day", "2".into()), ("name", "Fast Food".into())] => "Day 2: Fast Food")]
+    #[test_case("No args", &[("day", "2".into())] => "No args")]
+    fn format_string(template: &str, args: &[(&str, String)]) -> String {
+        super::format_string(template, args)
+    }
+}
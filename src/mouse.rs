@@ -1,13 +1,14 @@
 use bevy::{prelude::*, render::camera::RenderTarget};
+use iyes_loopless::prelude::*;
 
-use crate::render::MainCam;
+use crate::{nav::is_mouse_control, render::MainCam};
 
 pub struct MousePlugin;
 impl Plugin for MousePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CursorWorldPosition>()
             .init_resource::<CursorTouch>()
-            .add_system(store_cursor_pos)
+            .add_system(store_cursor_pos.run_if(is_mouse_control))
             .add_system(map_touch);
     }
 }
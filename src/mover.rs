@@ -1,11 +1,11 @@
 use crate::{
     board::Board,
-    coords::{get_world_coords_from_tile, TileCoords},
+    coords::{get_piece_world_coords, TileCoords},
     interaction::Interactable,
     mouse::CursorWorldPosition,
     piece::Piece,
     render::{ViewScale, ZIndex},
-    tile_placement::{Pieces, BOARD_SIZE_PX},
+    tile_placement::Pieces,
 };
 use bevy::prelude::*;
 use bevy_tweening::{Animator, AnimatorState};
@@ -30,13 +30,7 @@ fn process_movers(
         if let Ok(mut t) = moved_q.get_mut(mover.moved_e) {
             let z = t.translation.z;
             t.translation = if let Some(pos) = coords.tile_coords {
-                (get_world_coords_from_tile(pos)
-                    + Vec2::new(-BOARD_SIZE_PX / 2., BOARD_SIZE_PX / 2.)
-                    + Vec2::new(
-                        interactable.bounds.width() / 2.,
-                        -interactable.bounds.height() / 2.,
-                    ))
-                .extend(z)
+                get_piece_world_coords(pos, interactable.bounds.size()).extend(z)
             } else {
                 mover_t.translation
             };
@@ -0,0 +1,206 @@
+use crate::{
+    audio::AudioVolume, level::LevelEv, order::OrderTooltip, progress::TooltipProgress, GameState,
+};
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+pub struct MusicPlugin;
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<IntensityChannel>()
+            .init_resource::<MusicMix>()
+            .add_startup_system(load_music)
+            .add_system(switch_track)
+            .add_system(update_intensity.run_in_state(GameState::Playing))
+            .add_system(mix_music.after(switch_track).after(update_intensity));
+    }
+}
+
+const CROSSFADE_MS: u64 = 1200;
+/// Time for the ducking & intensity to fully change
+const MIX_FADE_S: f32 = 1.5;
+/// Music volume between the days
+const DUCKED_VOLUME: f32 = 0.4;
+/// Remaining order time (0-1) below which the intensity layer starts rising
+const INTENSITY_THRESHOLD: f32 = 0.3;
+
+struct MusicChannel;
+/// Layer playing along the day track, its volume follows the most urgent order
+struct IntensityChannel;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MusicTrack {
+    Menu,
+    Day,
+    Won,
+}
+
+impl MusicTrack {
+    fn from_state(state: &GameState) -> Option<Self> {
+        match state {
+            GameState::Loading => None,
+            GameState::Menu | GameState::Shop => Some(MusicTrack::Menu),
+            GameState::Playing => Some(MusicTrack::Day),
+            GameState::Won => Some(MusicTrack::Won),
+        }
+    }
+}
+
+struct MusicTracks {
+    menu: Handle<AudioSource>,
+    day: Handle<AudioSource>,
+    won: Handle<AudioSource>,
+    intensity: Handle<AudioSource>,
+}
+
+impl MusicTracks {
+    fn get_track(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::Menu => self.menu.clone(),
+            MusicTrack::Day => self.day.clone(),
+            MusicTrack::Won => self.won.clone(),
+        }
+    }
+}
+
+struct MusicMix {
+    track: Option<MusicTrack>,
+    ducked: bool,
+    duck: f32,
+    intensity: f32,
+    target_intensity: f32,
+}
+
+impl Default for MusicMix {
+    fn default() -> Self {
+        Self {
+            track: None,
+            ducked: false,
+            duck: 1.,
+            intensity: 0.,
+            target_intensity: 0.,
+        }
+    }
+}
+
+// the tracks aren't part of the loading state, so the game starts before the music is ready
+fn load_music(mut cmd: Commands, asset_server: Res<AssetServer>) {
+    cmd.insert_resource(MusicTracks {
+        menu: asset_server.load("audio/music_menu.ogg"),
+        day: asset_server.load("audio/music_day.ogg"),
+        won: asset_server.load("audio/music_won.ogg"),
+        // same tempo & length as the day track
+        intensity: asset_server.load("audio/music_intensity.ogg"),
+    });
+}
+
+fn switch_track(
+    state: Res<CurrentState<GameState>>,
+    tracks: Res<MusicTracks>,
+    mut mix: ResMut<MusicMix>,
+    mut lvl_evr: EventReader<LevelEv>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    intensity_channel: Res<AudioChannel<IntensityChannel>>,
+) {
+    let mut next_track = None;
+
+    for ev in lvl_evr.iter() {
+        match ev {
+            LevelEv::LevelIn => {
+                next_track = Some(MusicTrack::Day);
+                mix.ducked = false;
+                intensity_channel.stop().fade_out(get_crossfade());
+                intensity_channel
+                    .play(tracks.intensity.clone())
+                    .looped()
+                    .fade_in(get_crossfade());
+            }
+            LevelEv::LevelOut => {
+                mix.ducked = true;
+                mix.target_intensity = 0.;
+                intensity_channel.stop().fade_out(get_crossfade());
+            }
+            _ => {}
+        }
+    }
+
+    // the day track is started by the level events
+    if state.is_changed() && state.0 != GameState::Playing {
+        next_track = MusicTrack::from_state(&state.0);
+        mix.ducked = false;
+    }
+
+    if let Some(track) = next_track && mix.track != Some(track) {
+        music_channel.stop().fade_out(get_crossfade());
+        music_channel
+            .play(tracks.get_track(track))
+            .looped()
+            .fade_in(get_crossfade());
+        mix.track = Some(track);
+    }
+}
+
+fn get_crossfade() -> AudioTween {
+    AudioTween::linear(Duration::from_millis(CROSSFADE_MS))
+}
+
+fn update_intensity(
+    mut mix: ResMut<MusicMix>,
+    progress_q: Query<&TooltipProgress, With<OrderTooltip>>,
+) {
+    // order progress counts down
+    let remaining = progress_q
+        .iter()
+        .map(TooltipProgress::progress)
+        .fold(1., f32::min);
+
+    if !mix.ducked {
+        mix.target_intensity = get_intensity(remaining);
+    }
+}
+
+fn mix_music(
+    time: Res<Time>,
+    volume: Res<AudioVolume>,
+    mut mix: ResMut<MusicMix>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    intensity_channel: Res<AudioChannel<IntensityChannel>>,
+) {
+    let prev = (mix.duck, mix.intensity);
+    let max_delta = time.delta_seconds() / MIX_FADE_S;
+    let duck_target = if mix.ducked { DUCKED_VOLUME } else { 1. };
+    mix.duck = move_towards(mix.duck, duck_target, max_delta);
+    mix.intensity = move_towards(mix.intensity, mix.target_intensity, max_delta);
+
+    if volume.is_changed() || prev != (mix.duck, mix.intensity) {
+        let music_volume = volume.get_music_volume() * mix.duck as f64;
+        music_channel.set_volume(music_volume);
+        intensity_channel.set_volume(music_volume * mix.intensity as f64);
+    }
+}
+
+/// 0 until the remaining time drops below the threshold, then rises up to 1 at expiry
+fn get_intensity(remaining: f32) -> f32 {
+    (1. - remaining / INTENSITY_THRESHOLD).clamp(0., 1.)
+}
+
+fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    current + (target - current).clamp(-max_delta, max_delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(1. => 0.)]
+    #[test_case(INTENSITY_THRESHOLD => 0.)]
+    #[test_case(INTENSITY_THRESHOLD / 2. => 0.5)]
+    #[test_case(0. => 1.)]
+    fn get_intensity(remaining: f32) -> f32 {
+        super::get_intensity(remaining)
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{
+    board::Board,
+    coords::{get_piece_world_coords, TileCoords},
+    drag::Draggable,
+    input::{Action, ActionMap, Binding, RawInput},
+    interaction::{Interactable, InteractionGroup, InteractionState},
+    mouse::CursorWorldPosition,
+    piece::Piece,
+    tile_placement::{Pieces, BOARD_SIZE},
+    ui::MenuButton,
+};
+use bevy::{
+    input::{mouse::MouseButtonInput, InputSystem},
+    prelude::*,
+};
+
+pub struct NavPlugin;
+impl Plugin for NavPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavControl>()
+            .add_system_to_stage(CoreStage::PreUpdate, navigate.after(InputSystem));
+    }
+}
+
+const CARD_TARGET_GROUPS: [InteractionGroup; 6] = [
+    InteractionGroup::Cauldron,
+    InteractionGroup::Fire,
+    InteractionGroup::GridSection,
+    InteractionGroup::Order,
+    InteractionGroup::Piece,
+    InteractionGroup::Discard,
+];
+
+/// Cursorless keyboard/gamepad control.
+/// Drives the cursor position and holds the place action between presses,
+/// so picking, dragging and dropping go through the same systems as with a mouse.
+#[derive(Default)]
+pub struct NavControl {
+    pub active: bool,
+    place_held: bool,
+    focused_e: Option<Entity>,
+    dragged_e: Option<Entity>,
+    tile: UVec2,
+}
+
+impl NavControl {
+    pub fn is_place_held(&self) -> bool {
+        self.place_held
+    }
+}
+
+pub fn is_mouse_control(nav: Res<NavControl>) -> bool {
+    !nav.active
+}
+
+struct NavInput {
+    dir: IVec2,
+    confirm: bool,
+    next: bool,
+}
+
+fn get_nav_input(input: &RawInput, map: &ActionMap) -> NavInput {
+    let mut dir = IVec2::ZERO;
+
+    for (keys, key_dir) in [
+        ([KeyCode::Up, KeyCode::W], IVec2::Y),
+        ([KeyCode::Down, KeyCode::S], -IVec2::Y),
+        ([KeyCode::Left, KeyCode::A], -IVec2::X),
+        ([KeyCode::Right, KeyCode::D], IVec2::X),
+    ] {
+        if input.kb.any_just_pressed(keys) {
+            dir += key_dir;
+        }
+    }
+
+    for gamepad in input.gamepads.iter() {
+        for (btn, btn_dir) in [
+            (GamepadButtonType::DPadUp, IVec2::Y),
+            (GamepadButtonType::DPadDown, -IVec2::Y),
+            (GamepadButtonType::DPadLeft, -IVec2::X),
+            (GamepadButtonType::DPadRight, IVec2::X),
+        ] {
+            if input.pad.just_pressed(GamepadButton(*gamepad, btn)) {
+                dir += btn_dir;
+            }
+        }
+    }
+
+    NavInput {
+        dir: dir.clamp(-IVec2::ONE, IVec2::ONE),
+        // the mouse keeps using the cursor
+        confirm: map
+            .get_bindings(Action::Place)
+            .iter()
+            .any(|b| !matches!(b, Binding::Mouse(_)) && b.is_just_pressed(input)),
+        next: map.is_just_pressed(Action::NextTarget, input),
+    }
+}
+
+pub fn navigate(
+    mut nav: ResMut<NavControl>,
+    map: Res<ActionMap>,
+    kb_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    pad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut cursor_moved_evr: EventReader<CursorMoved>,
+    mut mouse_btn_evr: EventReader<MouseButtonInput>,
+    mut cursor: ResMut<CursorWorldPosition>,
+    state: Res<InteractionState>,
+    board: Res<Board>,
+    pieces: Res<Pieces>,
+    interactable_q: Query<(
+        Entity,
+        &Interactable,
+        &GlobalTransform,
+        Option<&Draggable>,
+        Option<&MenuButton>,
+    )>,
+    mut piece_q: Query<(&Piece, &mut TileCoords)>,
+) {
+    let input = get_nav_input(
+        &RawInput {
+            kb: &kb_input,
+            mouse: &mouse_input,
+            pad: &pad_input,
+            gamepads: &gamepads,
+        },
+        &map,
+    );
+    let mouse_used = cursor_moved_evr.iter().count() + mouse_btn_evr.iter().count() > 0;
+
+    if input.dir != IVec2::ZERO || input.confirm || input.next {
+        nav.active = true;
+    } else if mouse_used {
+        nav.active = false;
+    }
+
+    if !nav.active {
+        nav.place_held = false;
+        nav.focused_e = None;
+        nav.dragged_e = None;
+        return;
+    }
+
+    let target_pos = if let Some(dragged_e) = state.dragged_e {
+        if let Ok((piece, mut coords)) = piece_q.get_mut(dragged_e) {
+            // move the piece over the board tile by tile
+            let fields = &pieces.pieces[piece.0];
+            let max_tile = UVec2::new(
+                (BOARD_SIZE - fields.get_width()) as u32,
+                (BOARD_SIZE - fields.get_height()) as u32,
+            );
+
+            if nav.dragged_e != Some(dragged_e) {
+                nav.tile = max_tile / 2;
+            }
+
+            nav.tile = move_tile(nav.tile, input.dir, max_tile);
+            let tile = nav.tile;
+            let can_place = board
+                .can_place_piece(tile.x as usize, tile.y as usize, fields.get_fields())
+                .is_ok();
+            coords.tile_coords = can_place.then_some(tile);
+            coords.hovered_tile = Some(tile.as_ivec2());
+
+            interactable_q
+                .get(dragged_e)
+                .ok()
+                .map(|(_, i, ..)| get_piece_world_coords(tile, i.bounds.size()))
+        } else {
+            // cards go to cauldrons, fires or grid sections, soups to orders
+            let targets = interactable_q
+                .iter()
+                .filter(|(_, i, ..)| CARD_TARGET_GROUPS.contains(&i.group))
+                .map(|(e, i, t, ..)| (e, get_interactable_center(i, t)))
+                .collect();
+
+            update_focus(&mut nav, &input, cursor.position, targets)
+        }
+    } else {
+        let targets = interactable_q
+            .iter()
+            .filter(|(.., draggable, button)| {
+                draggable.is_some() || button.map_or(false, |b| b.enabled)
+            })
+            .map(|(e, i, t, ..)| (e, get_interactable_center(i, t)))
+            .collect();
+
+        update_focus(&mut nav, &input, cursor.position, targets)
+    };
+
+    nav.dragged_e = state.dragged_e;
+
+    if let Some(pos) = target_pos {
+        cursor.delta = pos - cursor.position;
+        cursor.position = pos;
+    }
+
+    if input.confirm {
+        // the 2nd press drops the dragged item
+        nav.place_held = state.dragged_e.is_none();
+    } else if state.dragged_e.is_none() {
+        // the press didn't start a drag (e.g. a button click)
+        nav.place_held = false;
+    }
+}
+
+fn get_interactable_center(interactable: &Interactable, t: &GlobalTransform) -> Vec2 {
+    t.translation().truncate() + (interactable.bounds.min + interactable.bounds.max) / 2.
+}
+
+fn update_focus(
+    nav: &mut NavControl,
+    input: &NavInput,
+    cursor_position: Vec2,
+    mut targets: Vec<(Entity, Vec2)>,
+) -> Option<Vec2> {
+    let focused = targets
+        .iter()
+        .find(|(e, _)| Some(*e) == nav.focused_e)
+        .cloned();
+
+    nav.focused_e = match focused {
+        Some((focused_e, _)) if input.next => {
+            // cycle the targets left to right, top to bottom
+            targets.sort_by(|(_, a), (_, b)| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+            let i = targets.iter().position(|(e, _)| *e == focused_e).unwrap();
+            Some(targets[(i + 1) % targets.len()].0)
+        }
+        Some((focused_e, pos)) if input.dir != IVec2::ZERO => {
+            let dir = input.dir.as_vec2();
+            find_in_direction(pos, dir, targets.iter().cloned()).or(Some(focused_e))
+        }
+        Some((focused_e, _)) => Some(focused_e),
+        // focus the target closest to the cursor
+        None => find_in_direction(cursor_position, Vec2::ZERO, targets.iter().cloned()),
+    };
+
+    targets
+        .iter()
+        .find(|(e, _)| Some(*e) == nav.focused_e)
+        .map(|(_, pos)| *pos)
+}
+
+/// Closest candidate in the given direction, a zero direction allows any candidate.
+/// Candidates within 45° of the direction are preferred, the rest are penalized for being off axis.
+fn find_in_direction<T>(
+    from: Vec2,
+    dir: Vec2,
+    candidates: impl Iterator<Item = (T, Vec2)>,
+) -> Option<T> {
+    let dir = dir.normalize_or_zero();
+
+    candidates
+        .filter_map(|(c, pos)| {
+            let offset = pos - from;
+
+            if dir == Vec2::ZERO {
+                Some((c, true, offset.length()))
+            } else {
+                let along = offset.dot(dir);
+                let across = (offset - dir * along).length();
+                (along > 0.5).then_some((c, along >= across, along + across * 2.))
+            }
+        })
+        .min_by(|(_, a_in_cone, a), (_, b_in_cone, b)| {
+            b_in_cone.cmp(a_in_cone).then(a.total_cmp(b))
+        })
+        .map(|(c, ..)| c)
+}
+
+/// Tile y goes down the board, so it's inverted compared to the direction
+fn move_tile(tile: UVec2, dir: IVec2, max_tile: UVec2) -> UVec2 {
+    UVec2::new(
+        (tile.x as i32 + dir.x).clamp(0, max_tile.x as i32) as u32,
+        (tile.y as i32 - dir.y).clamp(0, max_tile.y as i32) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Vec2::X => Some(1))]
+    #[test_case(-Vec2::X => Some(3))]
+    #[test_case(Vec2::Y => Some(2))]
+    #[test_case(-Vec2::Y => Some(4))]
+    #[test_case(Vec2::new(-1., -1.) => Some(3))]
+    #[test_case(Vec2::ZERO => Some(4))]
+    fn find_in_direction(dir: Vec2) -> Option<usize> {
+        let candidates = [
+            (0, Vec2::ZERO),
+            (1, Vec2::new(10., 2.)),
+            (2, Vec2::new(5., 20.)),
+            (3, Vec2::new(-30., 0.)),
+            (4, Vec2::new(1., -2.)),
+        ];
+
+        super::find_in_direction(
+            Vec2::ZERO,
+            dir,
+            candidates.into_iter().filter(|(c, _)| *c != 0),
+        )
+    }
+
+    #[test_case(UVec2::new(3, 3), IVec2::X => UVec2::new(4, 3))]
+    #[test_case(UVec2::new(3, 3), IVec2::Y => UVec2::new(3, 2))]
+    #[test_case(UVec2::new(0, 0), IVec2::new(-1, 1) => UVec2::new(0, 0))]
+    #[test_case(UVec2::new(6, 7), IVec2::new(1, -1) => UVec2::new(6, 7))]
+    fn move_tile(tile: UVec2, dir: IVec2) -> UVec2 {
+        super::move_tile(tile, dir, UVec2::new(6, 7))
+    }
+}
@@ -1,15 +1,22 @@
 use crate::{
-    assets::{Fonts, Sprites},
-    card::{Ingredient, CARD_SIZE},
-    cauldron::spawn_tooltip_ingredient,
+    assets::Sprites,
+    card::{CardEffect, Ingredient, CARD_SIZE},
+    cauldron::{spawn_tooltip_ingredient, DEFAULT_CAPACITY},
+    interaction::{Interactable, InteractionGroup, InteractionState},
     level::{CurrentLevel, LevelEv, Levels},
     list::{ListPlugin, ListPluginOptions},
+    localization::Localization,
     progress::TooltipProgress,
-    render::{ZIndex, COL_DARK, VIEW_PADDING, PADDED_VIEW_EXTENDS, VIEW_EXTENDS},
+    render::{ZIndex, VIEW_PADDING, PADDED_VIEW_EXTENDS, VIEW_EXTENDS},
+    settings::Settings,
+    theme::{Theme, ThemeColor},
+    time_scale::{TimeScale, TimeScaled},
+    tutorial::is_tutorial_blocking,
     tween::{
         delay_tween, get_relative_move_by_anim, get_relative_move_by_tween, FadeHierarchyBundle,
         TweenDoneAction,
     },
+    ui::Overlay,
     GameState,
 };
 use bevy::{prelude::*, utils::HashMap};
@@ -32,10 +39,18 @@ impl Plugin for OrderPlugin {
             .add_system_set(
                 ConditionSet::new()
                     .run_in_state(GameState::Playing)
+                    .run_in_state(Overlay::None)
+                    .run_if_not(is_tutorial_blocking)
                     .with_system(spawn_orders)
                     .with_system(update_order_progress)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
                     .with_system(show_order_tooltip)
                     .with_system(on_order_completed)
+                    .with_system(freeze_order)
                     .with_system(on_level_over)
                     .into(),
             );
@@ -44,9 +59,16 @@ impl Plugin for OrderPlugin {
 }
 
 pub const ORDER_DELAY_S: f32 = 0.5;
+/// Remaining time which triggers the slow-mo
+const SLOW_MO_REMAINING: Duration = Duration::from_millis(1500);
+/// How long the freeze card stops the order timer
+const FREEZE_S: f32 = 10.;
 
 pub enum OrderEv {
-    Completed(Entity),
+    /// Quality from 0 to 1 - only the exact matches are 1
+    Completed { order_e: Entity, quality: f32 },
+    /// Overcooked or spoiled before serving - holds the cauldron entity
+    SoupBurned(Entity),
 }
 
 pub struct SpecialOrder {
@@ -60,25 +82,85 @@ pub struct Order {
     timer: Timer,
     delay: Option<Timer>,
     tooltip_e: Option<Entity>,
+    slowed_down: bool,
+    /// The order timer doesn't run until this one finishes
+    frozen: Option<Timer>,
+}
+
+/// How far a cooked soup is from an order
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SoupMatch {
+    pub missing: u8,
+    /// More of an ordered ingredient than needed
+    pub extra: u8,
+    /// Ingredients the order doesn't have at all
+    pub wrong: u8,
+}
+
+impl SoupMatch {
+    pub fn is_exact(&self) -> bool {
+        self.missing == 0 && self.extra == 0 && self.wrong == 0
+    }
+
+    /// A wrong ingredient costs twice as much as a missing or an extra one
+    pub fn get_quality(&self, order_size: u8) -> f32 {
+        let penalty = self.missing + self.extra + self.wrong * 2;
+        (1. - penalty as f32 / order_size.max(1) as f32).max(0.)
+    }
 }
 
 impl Order {
-    pub fn is_equal(&self, ingredients: &[Ingredient]) -> bool {
-        self.ingredients.values().sum::<u8>() == ingredients.len() as u8
-            && self
-                .ingredients
-                .iter()
-                .all(|(i, count)| ingredients.iter().filter(|i2| i == *i2).count() as u8 == *count)
+    /// Wildcards fill in for whatever is missing
+    pub fn get_match(&self, ingredients: &[Ingredient]) -> SoupMatch {
+        let mut soup_match = SoupMatch::default();
+        let mut missing = 0;
+
+        for (ingredient, count) in self.ingredients.iter() {
+            let used = ingredients.iter().filter(|i| *i == ingredient).count() as u8;
+            missing += count.saturating_sub(used);
+            soup_match.extra += used.saturating_sub(*count);
+        }
+
+        let wildcards = ingredients
+            .iter()
+            .filter(|i| **i == Ingredient::Wildcard)
+            .count() as u8;
+        soup_match.missing = missing.saturating_sub(wildcards);
+        soup_match.extra += wildcards.saturating_sub(missing);
+        soup_match.wrong = ingredients
+            .iter()
+            .filter(|i| **i != Ingredient::Wildcard && !self.ingredients.contains_key(i))
+            .count() as u8;
+
+        soup_match
+    }
+
+    pub fn get_quality(&self, ingredients: &[Ingredient]) -> f32 {
+        self.get_match(ingredients)
+            .get_quality(self.ingredients.values().sum())
     }
 }
 #[derive(Component)]
 pub struct OrderTooltip;
 
+/// The order of the hovered tooltip, the order is a child of its tooltip
+pub fn get_hovered_order(
+    interaction_state: &InteractionState,
+    order_q: &Query<(Entity, &Parent), With<Order>>,
+) -> Option<Entity> {
+    let tooltip_e = interaction_state.get_first_hovered_entity(&InteractionGroup::Order)?;
+    order_q
+        .iter()
+        .find(|(_, p)| p.get() == tooltip_e)
+        .map(|(e, _)| e)
+}
+
 fn spawn_orders(
     mut cmd: Commands,
     lvls: Res<Levels>,
     mut lvl: ResMut<CurrentLevel>,
     time: Res<Time>,
+    time_scale: Res<TimeScale>,
     order_q: Query<(), With<Order>>,
     mut order_evw: EventWriter<LevelEv>,
 ) {
@@ -90,7 +172,7 @@ fn spawn_orders(
 
     let active_order_count = order_q.iter().len();
     if let Some(ref mut timer) = lvl.start_timer {
-        timer.tick(time.delta());
+        timer.tick(time_scale.scale_delta(time.delta()));
 
         if timer.finished() {
             lvl.start_timer = None;
@@ -98,7 +180,7 @@ fn spawn_orders(
     } else if active_order_count >= lvl_opts.max_simultaneous_orders as usize {
         // bail out if there're too many orders
     } else if lvl.order_count < (lvl_opts.total_order_count as usize) {
-        lvl.next_customer_timer.tick(time.delta());
+        lvl.next_customer_timer.tick(time_scale.scale_delta(time.delta()));
 
         if lvl.next_customer_timer.finished() || active_order_count == 0 {
             let mut rng = thread_rng();
@@ -155,7 +237,7 @@ fn spawn_orders(
             }
       
             let mut duration = ingredients.iter().map(|(i, count)| {
-                let ingredient_time = if *i as u8 >= Ingredient::Eggplant as u8 {
+                let ingredient_time = if i.is_rare() {
                     // more time for the rarer ingredients
                     50.
                 }
@@ -176,6 +258,8 @@ fn spawn_orders(
                 timer: Timer::from_seconds(duration, false),
                 delay: Some(Timer::from_seconds(ORDER_DELAY_S, false)),
                 tooltip_e: None,
+                slowed_down: false,
+                frozen: None,
             })
             .insert(Name::new("order"));
 
@@ -191,7 +275,8 @@ fn spawn_orders(
 fn show_order_tooltip(
     mut cmd: Commands,
     sprites: Res<Sprites>,
-    fonts: Res<Fonts>,
+    loc: Localization,
+    theme: Res<Theme>,
     mut order_q: Query<(Entity, &mut Order), Added<Order>>,
 ) {
     for (o_e, mut o) in order_q.iter_mut() {
@@ -200,7 +285,7 @@ fn show_order_tooltip(
             .iter()
             .enumerate()
             .map(|(i, (ingredient, count))| {
-                spawn_tooltip_ingredient(*ingredient, *count, i, -6.0, &mut cmd, &sprites, &fonts).0
+                spawn_tooltip_ingredient(*ingredient, *count, i, DEFAULT_CAPACITY, -6.0, &mut cmd, &sprites, &loc, &theme).0
             })
             .collect();
 
@@ -216,8 +301,10 @@ fn show_order_tooltip(
             })
             .insert(ZIndex::OrderTooltip)
             .insert(TooltipProgress::new(-1.5, true))
-            .insert_bundle(FadeHierarchyBundle::new(true, 450, COL_DARK))
+            .insert_bundle(FadeHierarchyBundle::new(true, 450, theme.get(ThemeColor::Dark)))
             .insert(OrderTooltip)
+            .insert(TimeScaled)
+            .insert(Interactable::new_rectangle(InteractionGroup::Order, Vec2::new(25., 14.)))
             .insert(Name::new("order_tooltip"))
             .push_children(&tooltip_ingredients)
             .add_child(o_e)
@@ -230,7 +317,9 @@ fn show_order_tooltip(
 fn update_order_progress(
     mut order_q: Query<&mut Order>,
     mut progress_q: Query<&mut TooltipProgress>,
+    mut sprite_q: Query<&mut Sprite, With<OrderTooltip>>,
     time: Res<Time>,
+    mut time_scale: ResMut<TimeScale>,
     mut order_evw: EventWriter<LevelEv>,
     mut lvl: ResMut<CurrentLevel>,
 ) {
@@ -238,12 +327,28 @@ fn update_order_progress(
         if let Some(tooltip_e) = o.tooltip_e {
             // initial delay before the actual timed progress starts
             if let Some(delay) = &mut o.delay {
-                delay.tick(time.delta());
+                delay.tick(time_scale.scale_delta(time.delta()));
                 if delay.just_finished() {
                     o.delay = None;
                 }
+            } else if let Some(frozen) = &mut o.frozen {
+                frozen.tick(time_scale.scale_delta(time.delta()));
+                if frozen.just_finished() {
+                    o.frozen = None;
+
+                    if let Ok(mut sprite) = sprite_q.get_mut(tooltip_e) {
+                        sprite.color = Color::WHITE;
+                    }
+                }
             } else {
-                o.timer.tick(time.delta());
+                o.timer.tick(time_scale.scale_order_delta(time.delta()));
+
+                // short slow-mo right before the order fails
+                if !o.slowed_down && o.timer.duration().saturating_sub(o.timer.elapsed()) <= SLOW_MO_REMAINING {
+                    o.slowed_down = true;
+                    time_scale.start_slow_mo();
+                }
+
                 if o.timer.just_finished() {
                     lvl.stopped = true;
                     order_evw.send(LevelEv::LevelOver { won: false });
@@ -260,14 +365,16 @@ fn on_order_completed(
     mut cmd: Commands,
     mut order_evr: EventReader<OrderEv>,
     order_q: Query<(Entity, &Parent)>,
+    settings: Res<Settings>,
 ) {
     for ev in order_evr.iter() {
-        if let OrderEv::Completed(o_e) = ev {
+        if let OrderEv::Completed { order_e: o_e, .. } = ev {
             if let Ok((o_e, o_p)) = order_q.get(*o_e) {
                 cmd.entity(o_e).despawn_recursive();
                 cmd.entity(o_p.get()).insert(get_relative_move_by_anim(
                     Vec3::X * 250.,
                     300,
+                    settings.reduced_motion,
                     Some(crate::tween::TweenDoneAction::DespawnRecursive),
                 ));
             }
@@ -275,11 +382,33 @@ fn on_order_completed(
     }
 }
 
+fn freeze_order(
+    mut card_evr: EventReader<CardEffect>,
+    mut order_q: Query<&mut Order>,
+    mut sprite_q: Query<&mut Sprite, With<OrderTooltip>>,
+    theme: Res<Theme>,
+) {
+    for ev in card_evr.iter() {
+        if let CardEffect::FreezeOrder { order_e } = ev
+            && let Ok(mut o) = order_q.get_mut(*order_e)
+        {
+            o.frozen = Some(Timer::from_seconds(FREEZE_S, false));
+
+            if let Some(tooltip_e) = o.tooltip_e
+                && let Ok(mut sprite) = sprite_q.get_mut(tooltip_e)
+            {
+                sprite.color = theme.get(ThemeColor::Frozen);
+            }
+        }
+    }
+}
+
 fn on_level_over(
     mut cmd: Commands,
     mut lvl_evr: EventReader<LevelEv>,
     order_q: Query<Entity, With<Order>>,
     order_tooltip_q: Query<Entity, With<OrderTooltip>>,
+    settings: Res<Settings>,
 ) {
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelOver { .. } = ev {
@@ -293,6 +422,7 @@ fn on_level_over(
                     get_relative_move_by_tween(
                         Vec3::X * 70.,
                         350,
+                        settings.reduced_motion,
                         EaseFunction::QuadraticIn,
                         Some(TweenDoneAction::DespawnRecursive),
                     ),
@@ -320,17 +450,50 @@ mod tests {
     #[test_case(vec![(Ingredient::Tomato, 1)], vec![Ingredient::Tomato, Ingredient::Tomato] => false)]
     #[test_case(vec![(Ingredient::Tomato, 2), (Ingredient::Potato, 1)], vec![Ingredient::Tomato, Ingredient::Potato] => false)]
     #[test_case(vec![(Ingredient::Tomato, 1)], vec![Ingredient::Tomato, Ingredient::Potato] => false)]
-    fn is_equal(
+    #[test_case(vec![(Ingredient::Tomato, 2), (Ingredient::Potato, 1)], vec![Ingredient::Wildcard, Ingredient::Potato, Ingredient::Tomato] => true)]
+    #[test_case(vec![(Ingredient::Tomato, 1)], vec![Ingredient::Wildcard] => true)]
+    #[test_case(vec![(Ingredient::Tomato, 1)], vec![Ingredient::Wildcard, Ingredient::Wildcard] => false)]
+    #[test_case(vec![(Ingredient::Tomato, 2)], vec![Ingredient::Wildcard, Ingredient::Potato] => false)]
+    fn is_exact(
         order_ingredients: Vec<(Ingredient, u8)>,
         flat_ingredient_list: Vec<Ingredient>,
     ) -> bool {
-        let order = Order {
+        get_order(order_ingredients).get_match(&flat_ingredient_list).is_exact()
+    }
+
+    #[test_case(vec![(Ingredient::Tomato, 2)], vec![Ingredient::Tomato] => (1, 0, 0))]
+    #[test_case(vec![(Ingredient::Tomato, 1)], vec![Ingredient::Tomato, Ingredient::Tomato, Ingredient::Wildcard] => (0, 2, 0))]
+    #[test_case(vec![(Ingredient::Tomato, 2), (Ingredient::Potato, 1)], vec![Ingredient::Tomato, Ingredient::Garlic] => (2, 0, 1))]
+    #[test_case(vec![(Ingredient::Tomato, 2)], vec![Ingredient::Wildcard, Ingredient::Garlic] => (1, 0, 1))]
+    fn get_match(
+        order_ingredients: Vec<(Ingredient, u8)>,
+        flat_ingredient_list: Vec<Ingredient>,
+    ) -> (u8, u8, u8) {
+        let soup_match = get_order(order_ingredients).get_match(&flat_ingredient_list);
+        (soup_match.missing, soup_match.extra, soup_match.wrong)
+    }
+
+    #[test_case(0, 0, 0, 3 => 1.)]
+    #[test_case(1, 0, 0, 4 => 0.75)]
+    #[test_case(0, 1, 1, 4 => 0.25)]
+    #[test_case(1, 0, 1, 2 => 0. ; "never negative")]
+    fn get_quality(missing: u8, extra: u8, wrong: u8, order_size: u8) -> f32 {
+        SoupMatch {
+            missing,
+            extra,
+            wrong,
+        }
+        .get_quality(order_size)
+    }
+
+    fn get_order(order_ingredients: Vec<(Ingredient, u8)>) -> Order {
+        Order {
             ingredients: order_ingredients.into_iter().collect(),
             delay: None,
             timer: Timer::default(),
             tooltip_e: None,
-        };
-
-        order.is_equal(&flat_ingredient_list)
+            slowed_down: false,
+            frozen: None,
+        }
     }
 }
@@ -0,0 +1,104 @@
+use crate::{
+    input::restart_level,
+    level::{CurrentLevel, LevelEv},
+    localization::Localization,
+    render::ZIndex,
+    theme::{Theme, ThemeColor},
+    ui::{spawn_button, spawn_panel, spawn_text, ButtonClickEv, Overlay, MENU_LINE_HEIGHT},
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(Overlay::Pause, spawn_pause_menu)
+            .add_exit_system(Overlay::Pause, despawn_pause_menu)
+            .add_system(on_pause_button_click.run_in_state(Overlay::Pause));
+    }
+}
+
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Resume,
+    Restart,
+    Controls,
+    Settings,
+}
+
+fn spawn_pause_menu(mut cmd: Commands, loc: Localization, theme: Res<Theme>) {
+    let mut children = vec![spawn_text(
+        &mut cmd,
+        &loc,
+        loc.get("menu.paused"),
+        Vec3::new(0., MENU_LINE_HEIGHT * 2., 0.1),
+        TextAlignment::CENTER,
+        ThemeColor::Light,
+    )];
+
+    for (i, (btn, text)) in [
+        (PauseButton::Resume, "menu.resume"),
+        (PauseButton::Restart, "menu.restart_day"),
+        (PauseButton::Controls, "menu.controls"),
+        (PauseButton::Settings, "menu.settings"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            loc.get(text),
+            Vec3::new(0., MENU_LINE_HEIGHT * (0.5 - i as f32), 0.1),
+            TextAlignment::CENTER,
+            80.,
+            ThemeColor::Light,
+            true,
+        );
+        cmd.entity(btn_e).insert(btn);
+        children.push(btn_e);
+    }
+
+    let panel_e = spawn_panel(
+        &mut cmd,
+        &theme,
+        Vec2::new(120., 90.),
+        ZIndex::Overlay,
+        "pause_menu",
+        &children,
+    );
+    cmd.entity(panel_e).insert(PauseMenu);
+}
+
+fn despawn_pause_menu(mut cmd: Commands, menu_q: Query<Entity, With<PauseMenu>>) {
+    for e in menu_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+fn on_pause_button_click(
+    mut cmd: Commands,
+    mut click_evr: EventReader<ButtonClickEv>,
+    button_q: Query<&PauseButton>,
+    mut lvl: ResMut<CurrentLevel>,
+    mut lvl_evw: EventWriter<LevelEv>,
+) {
+    for ev in click_evr.iter() {
+        if let Ok(btn) = button_q.get(ev.0) {
+            match btn {
+                PauseButton::Resume => cmd.insert_resource(NextState(Overlay::None)),
+                PauseButton::Restart => {
+                    restart_level(&mut lvl, &mut lvl_evw);
+                    cmd.insert_resource(NextState(Overlay::None));
+                }
+                PauseButton::Controls => cmd.insert_resource(NextState(Overlay::Controls)),
+                PauseButton::Settings => cmd.insert_resource(NextState(Overlay::Settings)),
+            }
+
+            break;
+        }
+    }
+}
@@ -11,8 +11,10 @@ use crate::{
     drag::Draggable,
     interaction::{Interactable, InteractionGroup},
     mover::Mover,
-    render::{ZIndex, COL_DARK},
+    render::ZIndex,
+    theme::{Theme, ThemeColor},
     tile_placement::TILE_SIZE,
+    time_scale::TimeScaled,
     tween::{delay_tween, get_relative_move_tween},
 };
 
@@ -81,10 +83,12 @@ impl PieceFields {
 
 pub fn spawn_piece(
     cmd: &mut Commands,
+    theme: &Theme,
     piece: &PieceFields,
     piece_index: usize,
     position: Vec2,
     tween_delay: u64,
+    reduced_motion: bool,
 ) {
     let size_h = TILE_SIZE / 2.;
     let corner = Vec2::new(
@@ -117,16 +121,18 @@ pub fn spawn_piece(
                         ..default()
                     },
                     DrawMode::Outlined {
-                        outline_mode: StrokeMode::new(COL_DARK, 1.),
-                        fill_mode: FillMode::color(Color::rgb_u8(254, 209, 113)),
+                        outline_mode: StrokeMode::new(theme.get(ThemeColor::Dark), 1.),
+                        fill_mode: FillMode::color(theme.get(ThemeColor::PieceFill)),
                     },
                     Transform::from_translation(Vec3::new(0., 63., pos.z)),
                 ))
                 .insert(Animator::new(delay_tween(
-                    get_relative_move_tween(pos, 450, None),
+                    get_relative_move_tween(pos, 450, reduced_motion, None),
                     tween_delay,
                 )))
                 .insert(FieldCoords(UVec2::new(x as u32, y as u32)))
+                // unparented when placed, so it's marked on its own
+                .insert(TimeScaled)
                 .insert(Name::new("field"));
             }
         })
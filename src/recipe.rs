@@ -0,0 +1,188 @@
+use crate::{card::Ingredient, hint::Hint};
+
+/// Water has to boil before anything cooks
+const BASE_COOK_TIME: f32 = 9.;
+const MAX_COOK_TIME: f32 = 30.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngredientCategory {
+    Vegetable,
+    Mushroom,
+    Spice,
+    /// Wildcards fit into any category
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IngredientProps {
+    /// Seconds added to the cook time for each piece
+    pub cook_time: f32,
+    /// The soup burns once more than `1 - burn_risk` of its cooking was done on a boosted fire
+    pub burn_risk: f32,
+    pub category: IngredientCategory,
+}
+
+impl Ingredient {
+    pub fn get_props(&self) -> IngredientProps {
+        let (cook_time, burn_risk, category) = match self {
+            Ingredient::Pumpkin => (2., 0., IngredientCategory::Vegetable),
+            Ingredient::Potato => (1.5, 0., IngredientCategory::Vegetable),
+            Ingredient::Tomato => (1., 0.1, IngredientCategory::Vegetable),
+            Ingredient::Eggplant => (1.5, 0.05, IngredientCategory::Vegetable),
+            Ingredient::Mushroom => (2.5, 0.1, IngredientCategory::Mushroom),
+            Ingredient::Garlic => (0.5, 0.3, IngredientCategory::Spice),
+            Ingredient::Wildcard => (1., 0., IngredientCategory::Any),
+        };
+
+        IngredientProps {
+            cook_time,
+            burn_risk,
+            category,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeRule {
+    /// Kinds of ingredients in a single soup
+    MaxKinds(usize),
+    MaxIngredients(usize),
+    MaxPerCategory(IngredientCategory, usize),
+}
+
+impl RecipeRule {
+    fn is_met(&self, ingredients: &[Ingredient]) -> bool {
+        match self {
+            RecipeRule::MaxKinds(max) => {
+                let mut kinds: Vec<_> = ingredients.to_vec();
+                kinds.sort_by_key(|i| *i as u8);
+                kinds.dedup();
+                kinds.len() <= *max
+            }
+            RecipeRule::MaxIngredients(max) => ingredients.len() <= *max,
+            RecipeRule::MaxPerCategory(category, max) => {
+                ingredients
+                    .iter()
+                    .filter(|i| i.get_props().category == *category)
+                    .count()
+                    <= *max
+            }
+        }
+    }
+
+    /// Hint shown when an ingredient breaks the rule
+    pub fn get_hint(&self) -> Hint {
+        match self {
+            RecipeRule::MaxKinds(_) => Hint::CauldronFull,
+            RecipeRule::MaxIngredients(_) => Hint::TooManyIngredients,
+            RecipeRule::MaxPerCategory(..) => Hint::CategoryFull,
+        }
+    }
+}
+
+/// What goes into a cauldron and how long it cooks
+#[derive(Debug, Clone)]
+pub struct RecipeRules {
+    pub rules: Vec<RecipeRule>,
+}
+
+impl Default for RecipeRules {
+    fn default() -> Self {
+        // the kinds are limited by the cauldron's capacity
+        Self { rules: Vec::new() }
+    }
+}
+
+impl RecipeRules {
+    /// Returns the first rule broken by adding the ingredients, starting with the capacity
+    pub fn check_add(
+        &self,
+        ingredients: &[Ingredient],
+        ingredient: Ingredient,
+        count: u8,
+        capacity: usize,
+    ) -> Result<(), RecipeRule> {
+        let mut soup = ingredients.to_vec();
+        soup.extend(std::iter::repeat(ingredient).take(count as usize));
+
+        match std::iter::once(RecipeRule::MaxKinds(capacity))
+            .chain(self.rules.iter().copied())
+            .find(|r| !r.is_met(&soup))
+        {
+            Some(rule) => Err(rule),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get_cook_time(&self, ingredients: &[Ingredient]) -> f32 {
+        let ingredients_time: f32 = ingredients.iter().map(|i| i.get_props().cook_time).sum();
+        (BASE_COOK_TIME + ingredients_time).min(MAX_COOK_TIME)
+    }
+
+    /// The riskiest ingredient decides - only the cooking done on a boosted fire burns soups
+    pub fn is_burned(&self, ingredients: &[Ingredient], boosted_share: f32) -> bool {
+        let risk = ingredients
+            .iter()
+            .map(|i| i.get_props().burn_risk)
+            .fold(0., f32::max);
+
+        risk > 0. && boosted_share > 1. - risk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const P: Ingredient = Ingredient::Potato;
+    const T: Ingredient = Ingredient::Tomato;
+    const G: Ingredient = Ingredient::Garlic;
+    const M: Ingredient = Ingredient::Mushroom;
+
+    #[test_case(vec![], P, 1 => Ok(()))]
+    #[test_case(vec![P, T, G], P, 3 => Ok(()) ; "kind already in")]
+    #[test_case(vec![P, T, G], M, 1 => Err(RecipeRule::MaxKinds(3)))]
+    fn check_add_default(
+        ingredients: Vec<Ingredient>,
+        ingredient: Ingredient,
+        count: u8,
+    ) -> Result<(), RecipeRule> {
+        RecipeRules::default().check_add(&ingredients, ingredient, count, 3)
+    }
+
+    #[test_case(vec![P, P], P, 1 => Ok(()))]
+    #[test_case(vec![P, P], P, 2 => Err(RecipeRule::MaxIngredients(3)))]
+    #[test_case(vec![G], G, 1 => Err(RecipeRule::MaxPerCategory(IngredientCategory::Spice, 1)))]
+    fn check_add(
+        ingredients: Vec<Ingredient>,
+        ingredient: Ingredient,
+        count: u8,
+    ) -> Result<(), RecipeRule> {
+        let rules = RecipeRules {
+            rules: vec![
+                RecipeRule::MaxIngredients(3),
+                RecipeRule::MaxPerCategory(IngredientCategory::Spice, 1),
+            ],
+        };
+
+        rules.check_add(&ingredients, ingredient, count, 3)
+    }
+
+    #[test_case(vec![] => 9.)]
+    #[test_case(vec![P, T, G] => 12.)]
+    #[test_case(vec![M; 20] => 30. ; "capped")]
+    fn get_cook_time(ingredients: Vec<Ingredient>) -> f32 {
+        RecipeRules::default().get_cook_time(&ingredients)
+    }
+
+    #[test_case(vec![P, T, G], 0.8 => true)]
+    #[test_case(vec![P, T, G], 0.6 => false)]
+    #[test_case(vec![P, T, G], 0. => false ; "not boosted")]
+    #[test_case(vec![T], 0.8 => false)]
+    #[test_case(vec![T], 0.95 => true)]
+    #[test_case(vec![P], 1. => false ; "no risk")]
+    fn is_burned(ingredients: Vec<Ingredient>, boosted_share: f32) -> bool {
+        RecipeRules::default().is_burned(&ingredients, boosted_share)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::GameState;
+use crate::{settings::Settings, GameState};
 use bevy::prelude::*;
 use bevy::render::camera::Viewport;
 use bevy::text::Text2dSize;
@@ -33,12 +33,6 @@ pub const VIEW_PADDING: f32 = 5.;
 pub const VIEW_EXTENDS: Vec2 = Vec2::new(VIEW_SIZE.x / 2., VIEW_SIZE.y / 2.);
 pub const PADDED_VIEW_EXTENDS: Vec2 =
     Vec2::new(VIEW_EXTENDS.x - VIEW_PADDING, VIEW_EXTENDS.y - VIEW_PADDING);
-pub const COL_DARK: Color = Color::rgb(0.2706, 0.2392, 0.2784);
-pub const COL_DARKER: Color = Color::rgb(0.1137, 0.0941, 0.0706);
-pub const COL_LIGHT: Color = Color::rgb(0.9372, 0.847, 0.7294);
-pub const COL_OUTLINE_HIGHLIGHTED: Color = Color::rgb(0.9647, 0.502, 0.2431);
-pub const COL_OUTLINE_HIGHLIGHTED_2: Color = Color::rgb(0.6745, 0.2352, 0.1333);
-pub const COL_OUTLINE_HOVERED_DRAG: Color = Color::rgb(0.3333, 0.5333, 0.247);
 
 #[derive(Component)]
 pub struct MainCam;
@@ -56,9 +50,12 @@ pub enum ZIndex {
     OrderTooltip,
     Explosion,
     Piece,
+    PlacementGhost,
     Card,
     Tooltip,
     Dragged,
+    Menu,
+    Overlay,
 }
 
 impl From<ZIndex> for f32 {
@@ -136,14 +133,23 @@ fn on_resize(
         &mut Camera,
     )>,
     windows: Res<Windows>,
+    settings: Res<Settings>,
 ) {
     let mut scale_changed = false;
     let window_resized = !resize_evr.is_empty();
+    let mut window_sizes: Vec<_> = resize_evr
+        .iter()
+        .map(|ev| Vec2::new(ev.width, ev.height))
+        .collect();
+
+    // the scale mode has been changed => rescale for the current window size
+    if settings.is_changed() && let Some(win) = windows.get_primary() {
+        window_sizes.push(Vec2::new(win.width(), win.height()));
+    }
 
-    for ev in resize_evr.iter() {
-        let new_scale = (Vec2::new(ev.width, ev.height) / VIEW_SIZE)
-            .floor()
-            .min_element() as u8;
+    for size in window_sizes {
+        let fit_scale = (size / VIEW_SIZE).floor().min_element() as u8;
+        let new_scale = settings.scale_mode.get_scale(fit_scale);
 
         if scale.0 != new_scale {
             scale.0 = new_scale;
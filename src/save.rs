@@ -1,59 +1,593 @@
-use bevy::prelude::*;
-use iyes_loopless::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use strum::IntoEnumIterator;
 use web_sys;
 
 use crate::{
-    level::{CurrentLevel, LevelEv, Levels},
+    audio::AudioVolume,
+    hint::Hint,
+    input::{Action, ActionMap, Binding},
+    level::{CurrentLevel, Levels},
+    settings::{Language, Palette, ScaleMode, Settings},
+    shop::Upgrade,
     tile_placement::Pieces,
 };
 
 pub struct SavePlugin;
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(store_level)
-            .add_startup_system(restore_level);
+        app.add_system(store_save)
+            .add_system(store_bindings)
+            .add_system(store_audio_volume)
+            .add_system(store_settings)
+            .add_startup_system(restore_save);
     }
 }
 
 const SAVE_KEY: &str = "rusty_lvl";
+const RESULTS_KEY: &str = "rusty_results";
+const BINDINGS_KEY: &str = "rusty_bindings";
+const AUDIO_KEY: &str = "rusty_audio";
+const SETTINGS_KEY: &str = "rusty_settings";
+const HINTS_KEY: &str = "rusty_hints";
+const SHOP_KEY: &str = "rusty_shop";
 
-fn store_level(mut lvl_evr: EventReader<LevelEv>, lvl: Res<CurrentLevel>) {
-    for ev in lvl_evr.iter() {
-        if let LevelEv::LevelOut = ev {
-            write_save(lvl.level_index);
+#[derive(Debug, Default)]
+pub struct SaveData {
+    pub unlocked_level: usize,
+    pub results: HashMap<usize, LevelResult>,
+    /// How many times each hint has been shown
+    pub hint_counts: HashMap<Hint, u8>,
+    /// Earned by serving orders, spent in the shop
+    pub coins: u32,
+    pub upgrades: HashMap<Upgrade, u8>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LevelResult {
+    pub best_time_s: Option<f32>,
+    pub best_orders_served: u8,
+    pub best_stars: u8,
+    pub best_score: u32,
+}
+
+impl LevelResult {
+    fn merge(&mut self, other: &LevelResult) {
+        self.best_time_s = match (self.best_time_s, other.best_time_s) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.best_orders_served = self.best_orders_served.max(other.best_orders_served);
+        self.best_stars = self.best_stars.max(other.best_stars);
+        self.best_score = self.best_score.max(other.best_score);
+    }
+}
+
+impl SaveData {
+    pub fn is_unlocked(&self, level_index: usize) -> bool {
+        level_index <= self.unlocked_level
+    }
+
+    pub fn unlock(&mut self, level_index: usize) {
+        self.unlocked_level = self.unlocked_level.max(level_index);
+    }
+
+    pub fn record_result(&mut self, level_index: usize, result: LevelResult) {
+        self.results.entry(level_index).or_default().merge(&result);
+    }
+
+    pub fn get_hint_count(&self, hint: Hint) -> u8 {
+        self.hint_counts.get(&hint).copied().unwrap_or(0)
+    }
+
+    pub fn record_hint(&mut self, hint: Hint) {
+        let count = self.hint_counts.entry(hint).or_default();
+        *count = count.saturating_add(1);
+    }
+
+    pub fn get_upgrade_level(&self, upgrade: Upgrade) -> u8 {
+        self.upgrades.get(&upgrade).copied().unwrap_or(0)
+    }
+
+    pub fn can_buy_upgrade(&self, upgrade: Upgrade) -> bool {
+        let level = self.get_upgrade_level(upgrade);
+        level < upgrade.get_max_level() && self.coins >= upgrade.get_price(level)
+    }
+
+    /// Returns false when the upgrade is maxed out or too expensive
+    pub fn buy_upgrade(&mut self, upgrade: Upgrade) -> bool {
+        if !self.can_buy_upgrade(upgrade) {
+            return false;
         }
+
+        let level = self.get_upgrade_level(upgrade);
+        self.coins -= upgrade.get_price(level);
+        self.upgrades.insert(upgrade, level + 1);
+        true
     }
 }
 
-fn restore_level(mut cmd: Commands, lvls: Res<Levels>, pieces: Res<Pieces>) {
-    let lvl = read_save();
+fn store_save(save: Res<SaveData>) {
+    if save.is_changed() {
+        write_item(SAVE_KEY, &save.unlocked_level.to_string());
+        write_item(RESULTS_KEY, &serialize_results(&save.results));
+        write_item(HINTS_KEY, &serialize_hint_counts(&save.hint_counts));
+        write_item(SHOP_KEY, &serialize_shop(save.coins, &save.upgrades));
+    }
+}
 
-    let range = lvls[lvl].pieces_range.clone();
-    let dist = pieces.get_distribution(range.clone());
-    cmd.insert_resource(CurrentLevel::new(
-        lvl,
+fn store_bindings(map: Res<ActionMap>) {
+    if map.is_changed() {
+        write_item(BINDINGS_KEY, &serialize_bindings(&map));
+    }
+}
+
+fn store_audio_volume(volume: Res<AudioVolume>) {
+    if volume.is_changed() {
+        write_item(AUDIO_KEY, &serialize_audio_volume(&volume));
+    }
+}
+
+fn store_settings(settings: Res<Settings>) {
+    if settings.is_changed() {
+        write_item(SETTINGS_KEY, &serialize_settings(&settings));
+    }
+}
+
+fn restore_save(mut cmd: Commands, lvls: Res<Levels>, pieces: Res<Pieces>) {
+    let unlocked_level = read_item(SAVE_KEY)
+        .and_then(|val| str::parse(&val).ok())
+        .unwrap_or(0)
+        .min(lvls.len() - 1);
+    let results = read_item(RESULTS_KEY)
+        .map(|val| parse_results(&val))
+        .unwrap_or_default();
+    let hint_counts = read_item(HINTS_KEY)
+        .map(|val| parse_hint_counts(&val))
+        .unwrap_or_default();
+    let (coins, upgrades) = read_item(SHOP_KEY)
+        .map(|val| parse_shop(&val))
+        .unwrap_or_default();
+
+    cmd.insert_resource(CurrentLevel::for_level(
+        unlocked_level,
         false,
-        dist,
-        range.map_or(0, |x| x.start),
+        &lvls,
+        &pieces,
     ));
+    cmd.insert_resource(SaveData {
+        unlocked_level,
+        results,
+        hint_counts,
+        coins,
+        upgrades,
+    });
+
+    let mut map = ActionMap::default();
+    if let Some(val) = read_item(BINDINGS_KEY) {
+        parse_bindings(&val, &mut map);
+    }
+    cmd.insert_resource(map);
+
+    cmd.insert_resource(
+        read_item(AUDIO_KEY)
+            .map(|val| parse_audio_volume(&val))
+            .unwrap_or_default(),
+    );
+
+    let mut settings = Settings::default();
+    if let Some(val) = read_item(SETTINGS_KEY) {
+        parse_settings(&val, &mut settings);
+    }
+    cmd.insert_resource(settings);
+}
+
+// results are stored as `index:time:orders:stars:score` entries separated by `;`
+// time is left empty for days which haven't been completed yet
+fn serialize_results(results: &HashMap<usize, LevelResult>) -> String {
+    let mut indices: Vec<_> = results.keys().collect();
+    indices.sort();
+
+    indices
+        .into_iter()
+        .map(|i| {
+            let res = &results[i];
+            format!(
+                "{i}:{}:{}:{}:{}",
+                res.best_time_s.map_or(String::new(), |t| t.to_string()),
+                res.best_orders_served,
+                res.best_stars,
+                res.best_score
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn parse_results(val: &str) -> HashMap<usize, LevelResult> {
+    val.split(';')
+        .filter_map(|entry| {
+            let mut parts = entry.split(':');
+            let index = str::parse(parts.next()?).ok()?;
+            let best_time_s = str::parse(parts.next()?).ok();
+            let best_orders_served = str::parse(parts.next()?).ok()?;
+            // stars & score were added later, so older saves might not have them
+            let best_stars = parts
+                .next()
+                .and_then(|stars| str::parse(stars).ok())
+                .unwrap_or(0);
+            let best_score = parts
+                .next()
+                .and_then(|score| str::parse(score).ok())
+                .unwrap_or(0);
+
+            Some((
+                index,
+                LevelResult {
+                    best_time_s,
+                    best_orders_served,
+                    best_stars,
+                    best_score,
+                },
+            ))
+        })
+        .collect()
+}
+
+// hint counts are stored as `hint=count` entries separated by `;`
+fn serialize_hint_counts(counts: &HashMap<Hint, u8>) -> String {
+    Hint::iter()
+        .filter_map(|hint| counts.get(&hint).map(|count| format!("{hint:?}={count}")))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+// hints which no longer exist are dropped
+fn parse_hint_counts(val: &str) -> HashMap<Hint, u8> {
+    val.split(';')
+        .filter_map(|entry| {
+            let (name, count) = entry.split_once('=')?;
+            let hint = Hint::iter().find(|h| format!("{h:?}") == name)?;
+            Some((hint, str::parse(count).ok()?))
+        })
+        .collect()
+}
+
+// the shop is stored as `coins=count` and `upgrade=level` entries separated by `;`
+fn serialize_shop(coins: u32, upgrades: &HashMap<Upgrade, u8>) -> String {
+    std::iter::once(format!("coins={coins}"))
+        .chain(Upgrade::iter().filter_map(|upgrade| {
+            upgrades
+                .get(&upgrade)
+                .map(|level| format!("{upgrade:?}={level}"))
+        }))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+// upgrades which no longer exist are dropped, the levels are capped
+fn parse_shop(val: &str) -> (u32, HashMap<Upgrade, u8>) {
+    let mut coins = 0;
+    let mut upgrades = HashMap::default();
+
+    for (name, val) in val.split(';').filter_map(|entry| entry.split_once('=')) {
+        if name == "coins" {
+            coins = str::parse(val).unwrap_or(0);
+        } else if let Some(upgrade) = Upgrade::iter().find(|u| format!("{u:?}") == name)
+            && let Ok(level) = str::parse::<u8>(val)
+        {
+            upgrades.insert(upgrade, level.min(upgrade.get_max_level()));
+        }
+    }
+
+    (coins, upgrades)
+}
+
+// bindings are stored as `action=binding,binding` entries separated by `;`
+fn serialize_bindings(map: &ActionMap) -> String {
+    Action::iter()
+        .filter(Action::is_rebindable)
+        .map(|action| {
+            let bindings: Vec<_> = map
+                .get_bindings(action)
+                .iter()
+                .map(|b| b.serialize())
+                .collect();
+            format!("{action:?}={}", bindings.join(","))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+// actions missing from the stored value keep their current bindings
+fn parse_bindings(val: &str, map: &mut ActionMap) {
+    let entries = val.split(';').filter_map(|entry| {
+        let (name, bindings) = entry.split_once('=')?;
+        let action = Action::iter().find(|a| format!("{a:?}") == name)?;
+        Some((action, bindings))
+    });
+
+    for (action, bindings) in entries {
+        map.set_bindings(
+            action,
+            bindings.split(',').filter_map(Binding::parse).collect(),
+        );
+    }
+}
+
+// volume is stored as `master:sfx:music:muted`
+fn serialize_audio_volume(volume: &AudioVolume) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        volume.master, volume.sfx, volume.music, volume.muted as u8
+    )
+}
+
+// invalid or missing values fall back to the defaults
+fn parse_audio_volume(val: &str) -> AudioVolume {
+    let mut volume = AudioVolume::default();
+    let mut parts = val.split(':');
+
+    for field in [&mut volume.master, &mut volume.sfx, &mut volume.music] {
+        if let Some(v) = parts.next().and_then(|v| str::parse::<f64>(v).ok()) {
+            *field = v.clamp(0., 1.);
+        }
+    }
+
+    if let Some(muted) = parts.next() {
+        volume.muted = muted == "1";
+    }
+
+    volume
+}
+
+// settings are stored as `name=value` entries separated by `;`
+fn serialize_settings(settings: &Settings) -> String {
+    [
+        format!("scale={}", settings.scale_mode.serialize()),
+        format!("reduced_motion={}", settings.reduced_motion as u8),
+        format!("relaxed={}", settings.relaxed as u8),
+        format!("palette={:?}", settings.palette),
+        format!("hints={}", settings.hints as u8),
+        format!("language={:?}", settings.language),
+    ]
+    .join(";")
+}
+
+// unknown entries or values are ignored
+fn parse_settings(val: &str, settings: &mut Settings) {
+    for (name, val) in val.split(';').filter_map(|entry| entry.split_once('=')) {
+        match name {
+            "scale" => {
+                if let Some(scale_mode) = ScaleMode::parse(val) {
+                    settings.scale_mode = scale_mode;
+                }
+            }
+            "reduced_motion" => settings.reduced_motion = val == "1",
+            "relaxed" => settings.relaxed = val == "1",
+            "palette" => {
+                if let Some(palette) = Palette::iter().find(|p| format!("{p:?}") == val) {
+                    settings.palette = palette;
+                }
+            }
+            "hints" => settings.hints = val == "1",
+            "language" => {
+                if let Some(language) = Language::iter().find(|l| format!("{l:?}") == val) {
+                    settings.language = language;
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 // todo: handle non-wasm, also error handling...
-fn write_save(level: usize) {
+fn write_item(key: &str, val: &str) {
     let storage = web_sys::window().unwrap().local_storage().unwrap().unwrap();
-    storage.set_item(SAVE_KEY, &level.to_string()).unwrap();
+    storage.set_item(key, val).unwrap();
 }
 
 // todo: handle non-wasm, also error handling...
-fn read_save() -> usize {
-    if cfg!(debug_assertions) {
-        // return 0;
+fn read_item(key: &str) -> Option<String> {
+    let storage = web_sys::window().unwrap().local_storage().unwrap().unwrap();
+    storage.get_item(key).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("" => Vec::<(usize, LevelResult)>::new())]
+    #[test_case("0:42.5:2" => vec![(0, LevelResult { best_time_s: Some(42.5), best_orders_served: 2, ..default() })])]
+    #[test_case("0:42.5:2:3" => vec![(0, LevelResult { best_time_s: Some(42.5), best_orders_served: 2, best_stars: 3, best_score: 0 })])]
+    #[test_case("0:42.5:2:3:450" => vec![(0, LevelResult { best_time_s: Some(42.5), best_orders_served: 2, best_stars: 3, best_score: 450 })])]
+    #[test_case("1::3:0" => vec![(1, LevelResult { best_time_s: None, best_orders_served: 3, ..default() })])]
+    #[test_case("0:10:1:2:x;garbage;2:x" => vec![(0, LevelResult { best_time_s: Some(10.), best_orders_served: 1, best_stars: 2, best_score: 0 })])]
+    fn parse_results(val: &str) -> Vec<(usize, LevelResult)> {
+        let mut res: Vec<_> = super::parse_results(val).into_iter().collect();
+        res.sort_by_key(|(i, _)| *i);
+        res
     }
 
-    let storage = web_sys::window().unwrap().local_storage().unwrap().unwrap();
+    #[test]
+    fn serialize_results_roundtrip() {
+        let results: HashMap<_, _> = [
+            (
+                3,
+                LevelResult {
+                    best_time_s: Some(95.25),
+                    best_orders_served: 5,
+                    best_stars: 2,
+                    best_score: 620,
+                },
+            ),
+            (
+                0,
+                LevelResult {
+                    best_time_s: None,
+                    best_orders_served: 1,
+                    best_stars: 0,
+                    best_score: 0,
+                },
+            ),
+        ]
+        .into();
+
+        let serialized = serialize_results(&results);
+
+        assert_eq!("0::1:0:0;3:95.25:5:2:620", serialized);
+        assert_eq!(results, super::parse_results(&serialized));
+    }
+
+    #[test]
+    fn serialize_hint_counts_roundtrip() {
+        let counts: HashMap<_, _> = [(Hint::WrongSoup, 2), (Hint::CauldronFull, 1)].into();
+
+        let serialized = serialize_hint_counts(&counts);
+
+        assert_eq!("CauldronFull=1;WrongSoup=2", serialized);
+        assert_eq!(counts, parse_hint_counts(&serialized));
+    }
+
+    #[test_case("" => Vec::<(Hint, u8)>::new())]
+    #[test_case("Gone=3;EmptySection=x;WrongSoup=1" => vec![(Hint::WrongSoup, 1)])]
+    fn parse_hint_counts(val: &str) -> Vec<(Hint, u8)> {
+        super::parse_hint_counts(val).into_iter().collect()
+    }
+
+    #[test]
+    fn serialize_shop_roundtrip() {
+        let upgrades: HashMap<_, _> = [(Upgrade::FireBoost, 2), (Upgrade::Capacity, 1)].into();
+
+        let serialized = serialize_shop(35, &upgrades);
+
+        assert_eq!("coins=35;Capacity=1;FireBoost=2", serialized);
+        assert_eq!((35, upgrades), parse_shop(&serialized));
+    }
+
+    #[test_case("" => (0, Vec::<(Upgrade, u8)>::new()))]
+    #[test_case("coins=x;Gone=1;CardSlot=9" => (0, vec![(Upgrade::CardSlot, 1)]))]
+    fn parse_shop(val: &str) -> (u32, Vec<(Upgrade, u8)>) {
+        let (coins, upgrades) = super::parse_shop(val);
+        (coins, upgrades.into_iter().collect())
+    }
+
+    #[test_case(30, 0 => (true, 0, 1))]
+    #[test_case(29, 0 => (false, 29, 0) ; "too expensive")]
+    #[test_case(500, 3 => (false, 500, 3) ; "maxed out")]
+    fn buy_upgrade(coins: u32, level: u8) -> (bool, u32, u8) {
+        let mut save = SaveData {
+            coins,
+            upgrades: [(Upgrade::CookSpeed, level)].into(),
+            ..default()
+        };
+
+        let bought = save.buy_upgrade(Upgrade::CookSpeed);
+        (
+            bought,
+            save.coins,
+            save.get_upgrade_level(Upgrade::CookSpeed),
+        )
+    }
+
+    #[test]
+    fn serialize_bindings_roundtrip() {
+        let mut map = ActionMap::default();
+        map.rebind(Action::Pause, Binding::Key(KeyCode::Escape));
+        map.set_bindings(Action::NextTarget, Vec::new());
+
+        let mut parsed = ActionMap::default();
+        parse_bindings(&serialize_bindings(&map), &mut parsed);
+
+        assert_eq!(map, parsed);
+    }
+
+    #[test]
+    fn parse_bindings_keeps_unknown() {
+        let mut map = ActionMap::default();
+        parse_bindings("Pause=k-Q,g-Nope;Dance=k-D", &mut map);
+
+        assert_eq!(&[Binding::Key(KeyCode::Q)], map.get_bindings(Action::Pause));
+        assert_eq!(
+            ActionMap::default().get_bindings(Action::Place),
+            map.get_bindings(Action::Place)
+        );
+    }
+
+    #[test]
+    fn serialize_audio_volume_roundtrip() {
+        let volume = AudioVolume {
+            master: 0.5,
+            sfx: 0.25,
+            music: 0.,
+            muted: true,
+        };
+
+        assert_eq!(volume, parse_audio_volume(&serialize_audio_volume(&volume)));
+    }
+
+    #[test]
+    fn serialize_settings_roundtrip() {
+        let settings = Settings {
+            scale_mode: ScaleMode::Fixed(2),
+            reduced_motion: true,
+            relaxed: true,
+            palette: Palette::Protanopia,
+            hints: false,
+            language: Language::Czech,
+        };
+
+        let mut parsed = Settings::default();
+        parse_settings(&serialize_settings(&settings), &mut parsed);
+
+        assert_eq!(settings, parsed);
+    }
+
+    #[test]
+    fn parse_settings_keeps_unknown() {
+        let mut settings = Settings::default();
+        parse_settings("scale=9;palette=Sepia;hints=0;speed=fast", &mut settings);
+
+        assert_eq!(
+            Settings {
+                hints: false,
+                ..default()
+            },
+            settings
+        );
+    }
+
+    #[test_case("" => AudioVolume::default())]
+    #[test_case("0.5:x:2" => AudioVolume { master: 0.5, music: 1., ..default() })]
+    fn parse_audio_volume(val: &str) -> AudioVolume {
+        super::parse_audio_volume(val)
+    }
+
+    #[test_case((Some(60.), 2, 1, 300), (Some(50.), 1, 2, 200) => (Some(50.), 2, 2, 300))]
+    #[test_case((None, 1, 0, 0), (Some(50.), 3, 1, 350) => (Some(50.), 3, 1, 350))]
+    #[test_case((Some(40.), 4, 3, 500), (None, 0, 0, 0) => (Some(40.), 4, 3, 500))]
+    fn merge_result(
+        (time, orders, stars, score): (Option<f32>, u8, u8, u32),
+        (other_time, other_orders, other_stars, other_score): (Option<f32>, u8, u8, u32),
+    ) -> (Option<f32>, u8, u8, u32) {
+        let mut res = LevelResult {
+            best_time_s: time,
+            best_orders_served: orders,
+            best_stars: stars,
+            best_score: score,
+        };
+        res.merge(&LevelResult {
+            best_time_s: other_time,
+            best_orders_served: other_orders,
+            best_stars: other_stars,
+            best_score: other_score,
+        });
 
-    match storage.get_item(SAVE_KEY).unwrap() {
-        Some(val) => str::parse(&val).unwrap_or(0),
-        None => 0,
+        (
+            res.best_time_s,
+            res.best_orders_served,
+            res.best_stars,
+            res.best_score,
+        )
     }
 }
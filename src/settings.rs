@@ -0,0 +1,390 @@
+use crate::{
+    audio::AudioVolume,
+    localization::Localization,
+    render::ZIndex,
+    theme::{Theme, ThemeColor},
+    ui::{spawn_button, spawn_panel, spawn_text, ButtonClickEv, Overlay, MENU_LINE_HEIGHT},
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+pub struct SettingsPlugin;
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_enter_system(Overlay::Settings, spawn_settings_menu)
+            .add_exit_system(Overlay::Settings, despawn_settings_menu)
+            .add_system(on_settings_button_click.run_in_state(Overlay::Settings))
+            .add_system(update_setting_texts.run_in_state(Overlay::Settings));
+    }
+}
+
+const LABEL_X: f32 = -100.;
+const VALUE_X: f32 = 10.;
+const LIST_TOP_Y: f32 = 58.;
+const VOLUME_STEP: f64 = 0.25;
+const MAX_FIXED_SCALE: u8 = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub scale_mode: ScaleMode,
+    pub reduced_motion: bool,
+    /// Slower order deadlines
+    pub relaxed: bool,
+    pub palette: Palette,
+    pub hints: bool,
+    pub language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            scale_mode: ScaleMode::Auto,
+            reduced_motion: false,
+            relaxed: false,
+            palette: Palette::Default,
+            hints: true,
+            language: Language::English,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Largest integer scale which fits the window
+    Auto,
+    Fixed(u8),
+}
+
+impl ScaleMode {
+    pub fn get_scale(&self, fit_scale: u8) -> u8 {
+        match self {
+            ScaleMode::Auto => fit_scale,
+            ScaleMode::Fixed(scale) => (*scale).min(fit_scale),
+        }
+        .max(1)
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            ScaleMode::Auto => ScaleMode::Fixed(1),
+            ScaleMode::Fixed(scale) if *scale < MAX_FIXED_SCALE => ScaleMode::Fixed(scale + 1),
+            ScaleMode::Fixed(_) => ScaleMode::Auto,
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        match self {
+            ScaleMode::Auto => "auto".into(),
+            ScaleMode::Fixed(scale) => scale.to_string(),
+        }
+    }
+
+    pub fn parse(val: &str) -> Option<Self> {
+        match val {
+            "auto" => Some(ScaleMode::Auto),
+            _ => str::parse(val)
+                .ok()
+                .filter(|scale| (1..=MAX_FIXED_SCALE).contains(scale))
+                .map(ScaleMode::Fixed),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Palette {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn get_key(&self) -> &'static str {
+        match self {
+            Palette::Default => "palette.default",
+            Palette::Deuteranopia => "palette.deuteranopia",
+            Palette::Protanopia => "palette.protanopia",
+            Palette::HighContrast => "palette.high_contrast",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Language {
+    English,
+    Czech,
+}
+
+impl Language {
+    /// Always in the language itself, so it can be found in any language
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Czech => "Čeština",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+enum SettingsRow {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    Mute,
+    Scale,
+    ReducedMotion,
+    Relaxed,
+    Palette,
+    Hints,
+    Language,
+}
+
+impl SettingsRow {
+    fn get_key(&self) -> &'static str {
+        match self {
+            SettingsRow::MasterVolume => "settings.volume",
+            SettingsRow::SfxVolume => "settings.sounds",
+            SettingsRow::MusicVolume => "settings.music",
+            SettingsRow::Mute => "settings.mute",
+            SettingsRow::Scale => "settings.scale",
+            SettingsRow::ReducedMotion => "settings.reduced_motion",
+            SettingsRow::Relaxed => "settings.relaxed",
+            SettingsRow::Palette => "settings.colors",
+            SettingsRow::Hints => "settings.hints",
+            SettingsRow::Language => "settings.language",
+        }
+    }
+
+    fn get_value(&self, settings: &Settings, volume: &AudioVolume, loc: &Localization) -> String {
+        match self {
+            SettingsRow::MasterVolume => format_volume(volume.master),
+            SettingsRow::SfxVolume => format_volume(volume.sfx),
+            SettingsRow::MusicVolume => format_volume(volume.music),
+            SettingsRow::Mute => loc.get(get_toggle_key(volume.muted)),
+            SettingsRow::Scale => match settings.scale_mode {
+                ScaleMode::Auto => loc.get("settings.scale_auto"),
+                ScaleMode::Fixed(scale) => format!("{scale}x"),
+            },
+            SettingsRow::ReducedMotion => loc.get(get_toggle_key(settings.reduced_motion)),
+            SettingsRow::Relaxed => loc.get(get_toggle_key(settings.relaxed)),
+            SettingsRow::Palette => loc.get(settings.palette.get_key()),
+            SettingsRow::Hints => loc.get(get_toggle_key(settings.hints)),
+            SettingsRow::Language => settings.language.get_label().into(),
+        }
+    }
+
+    fn change(&self, settings: &mut Settings, volume: &mut AudioVolume) {
+        match self {
+            SettingsRow::MasterVolume => volume.master = step_volume(volume.master),
+            SettingsRow::SfxVolume => volume.sfx = step_volume(volume.sfx),
+            SettingsRow::MusicVolume => volume.music = step_volume(volume.music),
+            SettingsRow::Mute => volume.muted = !volume.muted,
+            SettingsRow::Scale => settings.scale_mode = settings.scale_mode.next(),
+            SettingsRow::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            SettingsRow::Relaxed => settings.relaxed = !settings.relaxed,
+            SettingsRow::Palette => settings.palette = cycle(settings.palette),
+            SettingsRow::Hints => settings.hints = !settings.hints,
+            SettingsRow::Language => settings.language = cycle(settings.language),
+        }
+    }
+}
+
+#[derive(Component)]
+struct SettingsMenu;
+
+#[derive(Component, Clone, Copy)]
+enum SettingsButton {
+    Row(SettingsRow),
+    Back,
+}
+
+fn spawn_settings_menu(
+    mut cmd: Commands,
+    loc: Localization,
+    volume: Res<AudioVolume>,
+    theme: Res<Theme>,
+) {
+    let mut children = vec![spawn_text(
+        &mut cmd,
+        &loc,
+        loc.get("menu.settings"),
+        Vec3::new(0., LIST_TOP_Y + MENU_LINE_HEIGHT * 1.5, 0.1),
+        TextAlignment::CENTER,
+        ThemeColor::Light,
+    )];
+
+    for (i, row) in SettingsRow::iter().enumerate() {
+        let y = LIST_TOP_Y - i as f32 * MENU_LINE_HEIGHT;
+
+        children.push(spawn_text(
+            &mut cmd,
+            &loc,
+            loc.get(row.get_key()),
+            Vec3::new(LABEL_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            ThemeColor::Light,
+        ));
+
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            row.get_value(&loc.settings, &volume, &loc),
+            Vec3::new(VALUE_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            90.,
+            ThemeColor::Light,
+            true,
+        );
+        cmd.entity(btn_e).insert(SettingsButton::Row(row));
+        children.push(btn_e);
+    }
+
+    let back_e = spawn_button(
+        &mut cmd,
+        &loc,
+        loc.get("menu.back"),
+        Vec3::new(
+            0.,
+            LIST_TOP_Y - (SettingsRow::iter().count() as f32 + 0.5) * MENU_LINE_HEIGHT,
+            0.1,
+        ),
+        TextAlignment::CENTER,
+        50.,
+        ThemeColor::Light,
+        true,
+    );
+    cmd.entity(back_e).insert(SettingsButton::Back);
+    children.push(back_e);
+
+    let panel_e = spawn_panel(
+        &mut cmd,
+        &theme,
+        Vec2::new(230., 172.),
+        ZIndex::Overlay,
+        "settings_menu",
+        &children,
+    );
+    cmd.entity(panel_e).insert(SettingsMenu);
+}
+
+fn despawn_settings_menu(mut cmd: Commands, menu_q: Query<Entity, With<SettingsMenu>>) {
+    for e in menu_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+fn on_settings_button_click(
+    mut cmd: Commands,
+    mut click_evr: EventReader<ButtonClickEv>,
+    button_q: Query<&SettingsButton>,
+    mut settings: ResMut<Settings>,
+    mut volume: ResMut<AudioVolume>,
+    game_state: Res<CurrentState<GameState>>,
+) {
+    for ev in click_evr.iter() {
+        if let Ok(btn) = button_q.get(ev.0) {
+            match btn {
+                SettingsButton::Row(row) => row.change(&mut settings, &mut volume),
+                SettingsButton::Back => {
+                    cmd.insert_resource(NextState(if game_state.0 == GameState::Playing {
+                        Overlay::Pause
+                    } else {
+                        Overlay::None
+                    }))
+                }
+            }
+
+            break;
+        }
+    }
+}
+
+fn update_setting_texts(
+    mut cmd: Commands,
+    loc: Localization,
+    volume: Res<AudioVolume>,
+    mut language: Local<Option<Language>>,
+    mut button_q: Query<(&SettingsButton, &mut Text)>,
+) {
+    let settings = &loc.settings;
+    if language
+        .replace(settings.language)
+        .map_or(false, |l| l != settings.language)
+    {
+        // every label changes, so the menu is just spawned again
+        cmd.insert_resource(NextState(Overlay::Settings));
+    } else if settings.is_changed() || volume.is_changed() {
+        for (btn, mut txt) in button_q.iter_mut() {
+            if let SettingsButton::Row(row) = btn {
+                txt.sections[0].value = row.get_value(settings, &volume, &loc);
+            }
+        }
+    }
+}
+
+/// Next variant, wraps around
+pub fn cycle<T: IntoEnumIterator + PartialEq + Copy>(val: T) -> T {
+    let mut iter = T::iter().skip_while(|v| *v != val).skip(1);
+    iter.next().or_else(|| T::iter().next()).unwrap_or(val)
+}
+
+fn step_volume(volume: f64) -> f64 {
+    if volume >= 1. {
+        0.
+    } else {
+        ((volume / VOLUME_STEP).round() + 1.) * VOLUME_STEP
+    }
+    .min(1.)
+}
+
+fn format_volume(volume: f64) -> String {
+    format!("{}%", (volume * 100.).round())
+}
+
+fn get_toggle_key(on: bool) -> &'static str {
+    if on {
+        "settings.on"
+    } else {
+        "settings.off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0. => 0.25)]
+    #[test_case(0.6 => 0.75)]
+    #[test_case(0.75 => 1.)]
+    #[test_case(1. => 0.)]
+    fn step_volume(volume: f64) -> f64 {
+        super::step_volume(volume)
+    }
+
+    #[test_case(Palette::Default => Palette::Deuteranopia)]
+    #[test_case(Palette::HighContrast => Palette::Default)]
+    fn cycle(palette: Palette) -> Palette {
+        super::cycle(palette)
+    }
+
+    #[test_case(ScaleMode::Auto, 3 => 3)]
+    #[test_case(ScaleMode::Auto, 0 => 1)]
+    #[test_case(ScaleMode::Fixed(2), 3 => 2)]
+    #[test_case(ScaleMode::Fixed(4), 3 => 3)]
+    fn get_scale(mode: ScaleMode, fit_scale: u8) -> u8 {
+        mode.get_scale(fit_scale)
+    }
+
+    #[test_case("auto" => Some(ScaleMode::Auto))]
+    #[test_case("3" => Some(ScaleMode::Fixed(3)))]
+    #[test_case("0" => None)]
+    #[test_case("big" => None)]
+    fn parse_scale_mode(val: &str) -> Option<ScaleMode> {
+        ScaleMode::parse(val)
+    }
+}
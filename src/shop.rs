@@ -0,0 +1,260 @@
+use crate::{
+    card::MAX_CARDS,
+    cauldron::{CauldronSettings, MAX_CAPACITY},
+    localization::Localization,
+    render::ZIndex,
+    save::SaveData,
+    theme::{Theme, ThemeColor},
+    ui::{spawn_button, spawn_panel, spawn_text, ButtonClickEv, Overlay, MENU_LINE_HEIGHT},
+    GameState,
+};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+pub struct ShopPlugin;
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Shop, spawn_shop)
+            .add_exit_system(GameState::Shop, despawn_shop)
+            .add_system(
+                on_shop_button_click
+                    .run_in_state(GameState::Shop)
+                    .run_in_state(Overlay::None),
+            );
+    }
+}
+
+const LIST_X: f32 = -90.;
+const PRICE_X: f32 = 90.;
+const LIST_TOP_Y: f32 = 26.;
+const COOK_SPEED_STEP: f32 = 0.1;
+const FIRE_BOOST_STEP: f32 = 5.;
+
+/// Cauldron upgrades bought between the days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Component)]
+pub enum Upgrade {
+    /// Another kind of ingredient fits into a cauldron
+    Capacity,
+    CookSpeed,
+    /// Longer boost from a card thrown into the fire
+    FireBoost,
+    /// Another card in the hand
+    CardSlot,
+}
+
+impl Upgrade {
+    pub fn get_max_level(&self) -> u8 {
+        match self {
+            Upgrade::Capacity => 1,
+            Upgrade::CookSpeed => 3,
+            Upgrade::FireBoost => 3,
+            Upgrade::CardSlot => 1,
+        }
+    }
+
+    /// Price of the next level
+    pub fn get_price(&self, level: u8) -> u32 {
+        let base = match self {
+            Upgrade::Capacity => 80,
+            Upgrade::CookSpeed => 30,
+            Upgrade::FireBoost => 20,
+            Upgrade::CardSlot => 60,
+        };
+
+        base * (level as u32 + 1)
+    }
+
+    fn get_text_key(&self) -> &'static str {
+        match self {
+            Upgrade::Capacity => "shop.capacity",
+            Upgrade::CookSpeed => "shop.cook_speed",
+            Upgrade::FireBoost => "shop.fire_boost",
+            Upgrade::CardSlot => "shop.card_slot",
+        }
+    }
+}
+
+/// Applies the bought upgrades to the cauldron of a level
+pub fn upgrade_cauldron(settings: CauldronSettings, save: &SaveData) -> CauldronSettings {
+    let capacity = settings.capacity + save.get_upgrade_level(Upgrade::Capacity) as usize;
+    let cook_speed = 1. + save.get_upgrade_level(Upgrade::CookSpeed) as f32 * COOK_SPEED_STEP;
+    let fire_boost = save.get_upgrade_level(Upgrade::FireBoost) as f32 * FIRE_BOOST_STEP;
+
+    CauldronSettings {
+        capacity: capacity.min(MAX_CAPACITY),
+        cook_speed: settings.cook_speed * cook_speed,
+        fire_boost_time: settings.fire_boost_time + fire_boost,
+        ..settings
+    }
+}
+
+pub fn get_max_cards(save: &SaveData) -> usize {
+    MAX_CARDS + save.get_upgrade_level(Upgrade::CardSlot) as usize
+}
+
+#[derive(Component)]
+struct Shop;
+
+#[derive(Component)]
+struct NextDayButton;
+
+fn spawn_shop(mut cmd: Commands, loc: Localization, theme: Res<Theme>, save: Res<SaveData>) {
+    let mut children = vec![
+        spawn_text(
+            &mut cmd,
+            &loc,
+            loc.get("shop.title"),
+            Vec3::new(0., LIST_TOP_Y + MENU_LINE_HEIGHT * 2., 0.1),
+            TextAlignment::CENTER,
+            ThemeColor::Light,
+        ),
+        spawn_text(
+            &mut cmd,
+            &loc,
+            loc.format("shop.coins", &[("coins", save.coins.to_string())]),
+            Vec3::new(0., LIST_TOP_Y + MENU_LINE_HEIGHT, 0.1),
+            TextAlignment::CENTER,
+            ThemeColor::Light,
+        ),
+    ];
+
+    for (i, upgrade) in Upgrade::iter().enumerate() {
+        let y = LIST_TOP_Y - i as f32 * MENU_LINE_HEIGHT;
+        let level = save.get_upgrade_level(upgrade);
+        let maxed = level >= upgrade.get_max_level();
+        let can_buy = save.can_buy_upgrade(upgrade);
+
+        let btn_e = spawn_button(
+            &mut cmd,
+            &loc,
+            format!(
+                "{} {}/{}",
+                loc.get(upgrade.get_text_key()),
+                level,
+                upgrade.get_max_level()
+            ),
+            Vec3::new(LIST_X, y, 0.1),
+            TextAlignment::CENTER_LEFT,
+            PRICE_X - LIST_X - 40.,
+            if can_buy {
+                ThemeColor::Light
+            } else {
+                ThemeColor::Dark
+            },
+            can_buy,
+        );
+        cmd.entity(btn_e).insert(upgrade);
+        children.push(btn_e);
+
+        children.push(spawn_text(
+            &mut cmd,
+            &loc,
+            if maxed {
+                loc.get("shop.maxed")
+            } else {
+                loc.format(
+                    "shop.price",
+                    &[("price", upgrade.get_price(level).to_string())],
+                )
+            },
+            Vec3::new(PRICE_X, y, 0.1),
+            TextAlignment::CENTER_RIGHT,
+            ThemeColor::Light,
+        ));
+    }
+
+    let btn_e = spawn_button(
+        &mut cmd,
+        &loc,
+        loc.get("shop.next_day"),
+        Vec3::new(
+            0.,
+            LIST_TOP_Y - (Upgrade::iter().count() as f32 + 0.5) * MENU_LINE_HEIGHT,
+            0.1,
+        ),
+        TextAlignment::CENTER,
+        80.,
+        ThemeColor::Light,
+        true,
+    );
+    cmd.entity(btn_e).insert(NextDayButton);
+    children.push(btn_e);
+
+    let panel_e = spawn_panel(
+        &mut cmd,
+        &theme,
+        Vec2::new(200., 110.),
+        ZIndex::Menu,
+        "shop",
+        &children,
+    );
+    cmd.entity(panel_e).insert(Shop);
+}
+
+fn despawn_shop(mut cmd: Commands, shop_q: Query<Entity, With<Shop>>) {
+    for e in shop_q.iter() {
+        cmd.entity(e).despawn_recursive();
+    }
+}
+
+fn on_shop_button_click(
+    mut cmd: Commands,
+    mut click_evr: EventReader<ButtonClickEv>,
+    upgrade_q: Query<&Upgrade>,
+    next_day_q: Query<(), With<NextDayButton>>,
+    mut save: ResMut<SaveData>,
+) {
+    for ev in click_evr.iter() {
+        if let Ok(upgrade) = upgrade_q.get(ev.0) {
+            if save.buy_upgrade(*upgrade) {
+                // the shop is spawned again to show the new coins and prices
+                cmd.insert_resource(NextState(GameState::Shop));
+            }
+            break;
+        } else if next_day_q.get(ev.0).is_ok() {
+            cmd.insert_resource(NextState(GameState::Playing));
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(3, 0, 0, 0 => (3, 1., 15.))]
+    #[test_case(3, 1, 2, 3 => (4, 1.2, 30.))]
+    #[test_case(4, 1, 0, 0 => (5, 1., 15.) ; "bigger cauldron")]
+    #[test_case(4, 5, 0, 0 => (MAX_CAPACITY, 1., 15.) ; "capacity capped")]
+    fn upgrade_cauldron(
+        cauldron_capacity: usize,
+        capacity: u8,
+        cook_speed: u8,
+        fire_boost: u8,
+    ) -> (usize, f32, f32) {
+        let save = SaveData {
+            upgrades: [
+                (Upgrade::Capacity, capacity),
+                (Upgrade::CookSpeed, cook_speed),
+                (Upgrade::FireBoost, fire_boost),
+            ]
+            .into(),
+            ..default()
+        };
+
+        let c = super::upgrade_cauldron(
+            CauldronSettings::new(0.).with_capacity(cauldron_capacity),
+            &save,
+        );
+        (c.capacity, c.cook_speed, c.fire_boost_time)
+    }
+
+    #[test_case(Upgrade::CookSpeed, 0 => 30)]
+    #[test_case(Upgrade::CookSpeed, 2 => 90)]
+    #[test_case(Upgrade::CardSlot, 0 => 60)]
+    fn get_price(upgrade: Upgrade, level: u8) -> u32 {
+        upgrade.get_price(level)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::settings::{Palette, Settings};
+use bevy::prelude::*;
+
+pub struct ThemePlugin;
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Theme>()
+            .add_system_to_stage(CoreStage::PreUpdate, apply_palette)
+            .add_system(apply_themed_text);
+    }
+}
+
+/// Color role - the actual color depends on the palette picked in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeColor {
+    Transparent,
+    Dark,
+    Darker,
+    Light,
+    /// Cauldron drag target
+    Highlighted,
+    /// Fire drag target
+    Highlighted2,
+    HoveredDrag,
+    SectionHighlighted,
+    SectionHovered,
+    PieceFill,
+    GhostOk,
+    GhostTaken,
+    GhostOutOfBounds,
+    GhostClear,
+    Panel,
+    /// Tint of a frozen order
+    Frozen,
+}
+
+struct PaletteColors {
+    dark: Color,
+    darker: Color,
+    light: Color,
+    highlighted: Color,
+    highlighted_2: Color,
+    hovered_drag: Color,
+    piece_fill: Color,
+    ghost_ok: Color,
+    ghost_taken: Color,
+    frozen: Color,
+}
+
+const DEFAULT_COLORS: PaletteColors = PaletteColors {
+    dark: Color::rgb(0.2706, 0.2392, 0.2784),
+    darker: Color::rgb(0.1137, 0.0941, 0.0706),
+    light: Color::rgb(0.9372, 0.847, 0.7294),
+    highlighted: Color::rgb(0.9647, 0.502, 0.2431),
+    highlighted_2: Color::rgb(0.6745, 0.2352, 0.1333),
+    hovered_drag: Color::rgb(0.3333, 0.5333, 0.247),
+    piece_fill: Color::rgb(0.9961, 0.8196, 0.4431),
+    ghost_ok: Color::rgb(0.3333, 0.5333, 0.247),
+    ghost_taken: Color::rgb(0.6745, 0.2352, 0.1333),
+    frozen: Color::rgb(0.6, 0.85, 1.),
+};
+
+// orange/blue/yellow instead of the red/green pairs
+const DEUTERANOPIA_COLORS: PaletteColors = PaletteColors {
+    highlighted: Color::rgb(0.902, 0.6235, 0.),
+    highlighted_2: Color::rgb(0., 0.4471, 0.698),
+    hovered_drag: Color::rgb(0.9412, 0.8941, 0.2588),
+    ghost_ok: Color::rgb(0., 0.4471, 0.698),
+    ghost_taken: Color::rgb(0.8353, 0.3686, 0.),
+    ..DEFAULT_COLORS
+};
+
+// reds look dark, so the signal colors avoid them completely
+const PROTANOPIA_COLORS: PaletteColors = PaletteColors {
+    highlighted: Color::rgb(0.902, 0.6235, 0.),
+    highlighted_2: Color::rgb(0.3373, 0.7059, 0.9137),
+    hovered_drag: Color::rgb(0.9412, 0.8941, 0.2588),
+    ghost_ok: Color::rgb(0.3373, 0.7059, 0.9137),
+    ghost_taken: Color::rgb(0.902, 0.6235, 0.),
+    ..DEFAULT_COLORS
+};
+
+const HIGH_CONTRAST_COLORS: PaletteColors = PaletteColors {
+    dark: Color::BLACK,
+    darker: Color::BLACK,
+    light: Color::WHITE,
+    highlighted: Color::rgb(1., 0.9, 0.),
+    highlighted_2: Color::rgb(0., 0.9, 1.),
+    hovered_drag: Color::rgb(1., 0.2, 1.),
+    piece_fill: Color::rgb(1., 1., 0.6),
+    ghost_ok: Color::rgb(0., 0.9, 1.),
+    ghost_taken: Color::rgb(1., 0.2, 1.),
+    frozen: Color::rgb(0., 0.9, 1.),
+};
+
+#[derive(Default)]
+pub struct Theme {
+    palette: Palette,
+}
+
+impl Theme {
+    pub fn get(&self, color: ThemeColor) -> Color {
+        let colors = match self.palette {
+            Palette::Default => &DEFAULT_COLORS,
+            Palette::Deuteranopia => &DEUTERANOPIA_COLORS,
+            Palette::Protanopia => &PROTANOPIA_COLORS,
+            Palette::HighContrast => &HIGH_CONTRAST_COLORS,
+        };
+
+        let (mut col, alpha) = match color {
+            ThemeColor::Transparent => (Color::NONE, 0.),
+            ThemeColor::Dark => (colors.dark, 1.),
+            ThemeColor::Darker => (colors.darker, 1.),
+            ThemeColor::Light => (colors.light, 1.),
+            ThemeColor::Highlighted => (colors.highlighted, 1.),
+            ThemeColor::Highlighted2 => (colors.highlighted_2, 1.),
+            ThemeColor::HoveredDrag => (colors.hovered_drag, 1.),
+            ThemeColor::SectionHighlighted => (colors.highlighted, 0.4),
+            ThemeColor::SectionHovered => (colors.hovered_drag, 0.5),
+            ThemeColor::PieceFill => (colors.piece_fill, 1.),
+            ThemeColor::GhostOk => (colors.ghost_ok, 0.6),
+            ThemeColor::GhostTaken => (colors.ghost_taken, 0.6),
+            ThemeColor::GhostOutOfBounds => (colors.dark, 0.6),
+            ThemeColor::GhostClear => (colors.highlighted, 0.35),
+            ThemeColor::Panel => (colors.darker, 0.9),
+            ThemeColor::Frozen => (colors.frozen, 1.),
+        };
+
+        col.set_a(alpha);
+        col
+    }
+}
+
+/// Text colored by the theme, recolored when the palette changes
+#[derive(Component)]
+pub struct ThemedText(pub ThemeColor);
+
+fn apply_palette(settings: Res<Settings>, mut theme: ResMut<Theme>) {
+    if settings.is_changed() && theme.palette != settings.palette {
+        theme.palette = settings.palette;
+    }
+}
+
+fn apply_themed_text(
+    theme: Res<Theme>,
+    mut text_q: Query<(&ThemedText, ChangeTrackers<ThemedText>, &mut Text)>,
+) {
+    for (themed, themed_tracker, mut txt) in text_q.iter_mut() {
+        if theme.is_changed() || themed_tracker.is_added() {
+            for s in txt.sections.iter_mut() {
+                s.style.color = theme.get(themed.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn signal_colors_differ() {
+        for palette in Palette::iter() {
+            let theme = Theme { palette };
+            let signals = [
+                ThemeColor::Highlighted,
+                ThemeColor::Highlighted2,
+                ThemeColor::HoveredDrag,
+            ];
+
+            for (i, a) in signals.iter().enumerate() {
+                for b in signals.iter().skip(i + 1) {
+                    assert_ne!(theme.get(*a), theme.get(*b), "{palette:?} {a:?} {b:?}");
+                }
+            }
+
+            assert_ne!(
+                theme.get(ThemeColor::GhostOk),
+                theme.get(ThemeColor::GhostTaken),
+                "{palette:?}"
+            );
+        }
+    }
+}
@@ -2,17 +2,23 @@ use crate::{
     anim::SheetAnimation,
     assets::Sprites,
     board::{Board, BoardClear, BoardClearQueue},
-    card::{spawn_card, Card, CardEffect, MAX_CARDS},
+    card::{CardEffect, CardKind},
     coords::TileCoords,
     drag::Dragged,
+    hand::CardDropQueue,
+    input::Action,
+    interaction::InteractionEv,
     level::{CurrentLevel, LevelEv, Levels},
     mover::Mover,
     piece::{spawn_piece, FieldCoords, Piece, PieceFields, PlacedFieldIndex},
     render::ZIndex,
+    settings::Settings,
+    theme::Theme,
+    time_scale::TimeScaled,
     tween::{
-        delay_tween, get_relative_fade_spritesheet_tween, get_relative_move_by_tween,
-        get_relative_move_tween, get_relative_spritesheet_color_anim, get_scale_tween,
-        TweenDoneAction,
+        delay_tween, get_relative_fade_spritesheet_tween, get_relative_move_anim,
+        get_relative_move_by_tween, get_relative_move_tween, get_relative_spritesheet_color_anim,
+        get_scale_tween, TweenDoneAction,
     },
     GameState,
 };
@@ -28,7 +34,6 @@ pub const BOARD_SIZE: usize = 9;
 pub const TILE_SIZE: f32 = BOARD_SIZE_PX / BOARD_SIZE as f32;
 pub const SECTION_SIZE: usize = 3;
 pub const BOARD_SHIFT: Vec3 = Vec3::new(-91.0, -26., 0.);
-pub const CARDS_PER_CLEAR: usize = 2;
 
 pub struct TilePlacementPlugin;
 impl Plugin for TilePlacementPlugin {
@@ -90,6 +95,7 @@ impl Plugin for TilePlacementPlugin {
                 //     1,
                 // ),
             ]))
+            .add_event::<BoardEv>()
             .init_resource::<BoardClearQueue>()
             .add_system(fill_piece_queue.run_in_state(GameState::Playing))
             .add_system_to_stage(
@@ -97,11 +103,21 @@ impl Plugin for TilePlacementPlugin {
                 process_clear_queue.run_not_in_state(GameState::Loading),
             )
             .add_system(drop_piece.run_not_in_state(GameState::Loading))
+            .add_system(cancel_piece_drag.run_not_in_state(GameState::Loading))
             .add_system(on_level_over.run_in_state(GameState::Playing))
-            .add_system(clear_section_special.run_in_state(GameState::Playing));
+            .add_system(clear_section_special.run_in_state(GameState::Playing))
+            .add_system(reroll_pieces.run_in_state(GameState::Playing));
     }
 }
 
+pub enum BoardEv {
+    PiecePlaced,
+    Cleared(BoardClear),
+}
+
+#[derive(Component)]
+pub struct TileExplosion;
+
 pub struct Pieces {
     pub pieces: Vec<PieceFields>,
     distribution: Vec<usize>,
@@ -137,6 +153,7 @@ pub fn spawn_tile_explosion(cmd: &mut Commands, sprites: &Sprites, position: Vec
         ..default()
     })
     .insert(ZIndex::Explosion)
+    .insert(TileExplosion)
     .insert(
         SheetAnimation::new(65)
             .with_despawn_on_completion()
@@ -151,6 +168,8 @@ fn fill_piece_queue(
     pieces: Res<Pieces>,
     pieces_q: Query<Entity, With<Piece>>,
     lvl: Res<CurrentLevel>,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
 ) {
     if !lvl.stopped && lvl.has_started() && pieces_q.iter().len() == 0 {
         let mut rng = rand::thread_rng();
@@ -160,6 +179,7 @@ fn fill_piece_queue(
             let piece = &pieces.pieces[piece_i];
             spawn_piece(
                 &mut cmd,
+                &theme,
                 piece,
                 piece_i,
                 Vec2::new(
@@ -170,6 +190,7 @@ fn fill_piece_queue(
                         + 25.,
                 ),
                 i * 150,
+                settings.reduced_motion,
             );
         }
     }
@@ -177,7 +198,7 @@ fn fill_piece_queue(
 
 fn drop_piece(
     mut cmd: Commands,
-    mouse_input: Res<Input<MouseButton>>,
+    actions: Res<Input<Action>>,
     mut board: ResMut<Board>,
     mut clear_queue: ResMut<BoardClearQueue>,
     pieces: Res<Pieces>,
@@ -186,8 +207,10 @@ fn drop_piece(
     child_q: Query<&Children>,
     field_q: Query<&FieldCoords>,
     mut transform_q: Query<(&mut Transform, &GlobalTransform)>,
+    mut board_evw: EventWriter<BoardEv>,
+    settings: Res<Settings>,
 ) {
-    if mouse_input.just_released(MouseButton::Left) {
+    if actions.just_released(Action::Place) {
         for (dragged_e, piece, coords, mover) in dragged_query.iter() {
             let mut e_cmd = cmd.entity(dragged_e);
             e_cmd.remove::<Dragged>();
@@ -199,6 +222,7 @@ fn drop_piece(
                     pieces.pieces[piece.0].get_fields(),
                 ) {
                     e_cmd.despawn_recursive();
+                    board_evw.send(BoardEv::PiecePlaced);
                     let mut rng = rand::thread_rng();
 
                     if let Ok(children) = child_q.get(mover.moved_e) {
@@ -224,6 +248,7 @@ fn drop_piece(
                                     ..default()
                                 })
                                 .insert(PlacedFieldIndex(tile_coords))
+                                .insert(TimeScaled)
                                 .insert(Animator::new(delay_tween(
                                     get_relative_fade_spritesheet_tween(Color::WHITE, 350, None),
                                     tween_delay,
@@ -240,6 +265,7 @@ fn drop_piece(
                                         Vec3::ZERO,
                                         EaseFunction::QuadraticIn,
                                         400,
+                                        settings.reduced_motion,
                                         Some(TweenDoneAction::DespawnRecursive),
                                     ),
                                     tween_delay,
@@ -259,17 +285,48 @@ fn drop_piece(
     }
 }
 
+fn cancel_piece_drag(
+    mut cmd: Commands,
+    mut interaction_evr: EventReader<InteractionEv>,
+    mut piece_q: Query<(&Transform, &mut TileCoords), With<Piece>>,
+    settings: Res<Settings>,
+) {
+    for ev in interaction_evr.iter() {
+        if let InteractionEv::DragCancel(drag_data) = ev {
+            if let Ok((t, mut coords)) = piece_q.get_mut(drag_data.e) {
+                // the moved piece follows the mover back to the hand
+                coords.tile_coords = None;
+                coords.hovered_tile = None;
+                cmd.entity(drag_data.e).insert(get_relative_move_anim(
+                    drag_data.origin.extend(t.translation.z),
+                    300,
+                    settings.reduced_motion,
+                    None,
+                ));
+            }
+        }
+    }
+}
+
 fn process_clear_queue(
     mut cmd: Commands,
     mut queue: ResMut<BoardClearQueue>,
     mut board: ResMut<Board>,
     sprites: Res<Sprites>,
     field_q: Query<(Entity, &PlacedFieldIndex, &GlobalTransform)>,
-    card_q: Query<&Card>,
+    mut drops: ResMut<CardDropQueue>,
+    mut board_evw: EventWriter<BoardEv>,
+    mut lvl: ResMut<CurrentLevel>,
+    lvls: Res<Levels>,
 ) {
     if queue.is_changed() {
+        let ingredient_map = &lvls[lvl.level_index].ingredient_map;
+        let drop_table = &lvls[lvl.level_index].card_drops;
         let mut cleared_indices: Vec<usize> = Vec::default();
-        let mut allowed_card_spawn_count = MAX_CARDS.saturating_sub(card_q.iter().len());
+        let mut card_clear_count = 0;
+        let mut section_cleared = false;
+        let mut rng = thread_rng();
+
         while let Some(c) = queue.queue.pop_front() {
             let mut spawn_cards = true;
 
@@ -285,17 +342,27 @@ fn process_clear_queue(
                 }
             }
 
-            if spawn_cards {
-                for _ in 0..CARDS_PER_CLEAR {
-                    if allowed_card_spawn_count > 0 {
-                        spawn_card(&mut cmd, &sprites, &c);
-                        allowed_card_spawn_count -= 1;
-                    }
+            if spawn_cards && let Some(ingredient) = ingredient_map.get_ingredient(&c) {
+                card_clear_count += 1;
+                section_cleared |= matches!(c, BoardClear::Section { .. });
+
+                for _ in 0..drop_table.roll_ingredient_count(ingredient, &mut rng) {
+                    drops.queue.push_back(CardKind::Ingredient(ingredient));
                 }
             }
+
+            board_evw.send(BoardEv::Cleared(c));
         }
 
-        let mut rng = thread_rng();
+        if let Some(kind) = drop_table.roll_special(
+            card_clear_count,
+            section_cleared,
+            lvl.special_card_count,
+            &mut rng,
+        ) {
+            drops.queue.push_back(kind);
+            lvl.special_card_count += 1;
+        }
 
         for (i, (e, _, t)) in field_q
             .iter()
@@ -331,12 +398,37 @@ fn clear_section_special(
     }
 }
 
+fn reroll_pieces(
+    mut cmd: Commands,
+    mut card_evr: EventReader<CardEffect>,
+    piece_q: Query<(Entity, &Mover), With<Piece>>,
+    settings: Res<Settings>,
+) {
+    // fill_piece_queue spawns new ones once the hand is empty
+    if card_evr.iter().any(|ev| matches!(ev, CardEffect::Reroll)) {
+        for (i, (e, mover)) in piece_q.iter().enumerate() {
+            cmd.entity(e).despawn_recursive();
+            cmd.entity(mover.moved_e).insert(Animator::new(delay_tween(
+                get_relative_move_by_tween(
+                    Vec3::Y * 113.,
+                    350,
+                    settings.reduced_motion,
+                    EaseFunction::CircularIn,
+                    Some(TweenDoneAction::DespawnRecursive),
+                ),
+                i as u64 * 100,
+            )));
+        }
+    }
+}
+
 fn on_level_over(
     mut cmd: Commands,
     mut lvl_evr: EventReader<LevelEv>,
     mut board: ResMut<Board>,
     field_q: Query<Entity, With<PlacedFieldIndex>>,
     piece_q: Query<(Entity, &Mover), With<Piece>>,
+    settings: Res<Settings>,
 ) {
     for ev in lvl_evr.iter() {
         if let LevelEv::LevelOver { .. } = ev {
@@ -359,6 +451,7 @@ fn on_level_over(
                     get_relative_move_by_tween(
                         Vec3::Y * 113.,
                         350,
+                        settings.reduced_motion,
                         EaseFunction::CircularIn,
                         Some(TweenDoneAction::DespawnRecursive),
                     ),
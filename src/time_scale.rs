@@ -0,0 +1,183 @@
+use crate::{input::Action, settings::Settings};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::DrawMode;
+use bevy_tweening::Animator;
+use std::time::Duration;
+
+pub struct TimeScalePlugin;
+impl Plugin for TimeScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeScale>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_time_scale)
+            .add_system_set_to_stage(
+                CoreStage::PreUpdate,
+                SystemSet::new()
+                    .after(update_time_scale)
+                    .with_system(scale_animators::<Transform>)
+                    .with_system(scale_animators::<Sprite>)
+                    .with_system(scale_animators::<TextureAtlasSprite>)
+                    .with_system(scale_animators::<Text>)
+                    .with_system(scale_animators::<DrawMode>),
+            );
+
+        if cfg!(debug_assertions) {
+            app.add_system(fast_forward);
+        }
+    }
+}
+
+/// Order timers run this much slower in the relaxed mode
+const RELAXED_ORDER_SCALE: f32 = 0.6;
+const SLOW_MO_SCALE: f32 = 0.35;
+/// Real time duration of the slow-mo
+const SLOW_MO_S: f32 = 1.2;
+const FAST_FORWARD_SPEED: f32 = 4.;
+
+/// Its animators and the ones of its descendants follow the time scale,
+/// the rest (menus, overlays, the hand) runs in real time
+#[derive(Component)]
+pub struct TimeScaled;
+
+/// Gameplay speed, the game systems scale their timers by it (not the UI or input)
+#[derive(Debug)]
+pub struct TimeScale {
+    /// Debug fast-forward
+    pub speed: f32,
+    /// Slower order deadlines, mirrored from the settings
+    relaxed: bool,
+    slow_mo: Option<Timer>,
+    scale: f32,
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self {
+            speed: 1.,
+            relaxed: false,
+            slow_mo: None,
+            scale: 1.,
+        }
+    }
+}
+
+impl TimeScale {
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn scale_delta(&self, delta: Duration) -> Duration {
+        delta.mul_f32(self.scale)
+    }
+
+    pub fn scale_order_delta(&self, delta: Duration) -> Duration {
+        self.scale_delta(delta).mul_f32(if self.relaxed {
+            RELAXED_ORDER_SCALE
+        } else {
+            1.
+        })
+    }
+
+    pub fn start_slow_mo(&mut self) {
+        self.slow_mo = Some(Timer::from_seconds(SLOW_MO_S, false));
+    }
+
+    fn compute_scale(&self) -> f32 {
+        self.speed
+            * if self.slow_mo.is_some() {
+                SLOW_MO_SCALE
+            } else {
+                1.
+            }
+    }
+}
+
+fn update_time_scale(time: Res<Time>, settings: Res<Settings>, mut time_scale: ResMut<TimeScale>) {
+    if settings.is_changed() && time_scale.relaxed != settings.relaxed {
+        time_scale.relaxed = settings.relaxed;
+    }
+
+    // the slow-mo runs in real time
+    if let Some(slow_mo) = &mut time_scale.slow_mo {
+        slow_mo.tick(time.delta());
+
+        if slow_mo.finished() {
+            time_scale.slow_mo = None;
+        }
+    }
+
+    let scale = time_scale.compute_scale();
+    if time_scale.scale != scale {
+        time_scale.scale = scale;
+    }
+}
+
+// covers the newly added animators too
+fn scale_animators<T: Component>(
+    time_scale: Res<TimeScale>,
+    mut animator_q: Query<(Entity, &mut Animator<T>)>,
+    scaled_q: Query<(), With<TimeScaled>>,
+    parent_q: Query<&Parent>,
+) {
+    for (e, mut animator) in animator_q.iter_mut() {
+        let speed = if is_time_scaled(e, &scaled_q, &parent_q) {
+            time_scale.get_scale()
+        } else {
+            1.
+        };
+
+        if animator.speed() != speed {
+            animator.set_speed(speed);
+        }
+    }
+}
+
+fn is_time_scaled(
+    e: Entity,
+    scaled_q: &Query<(), With<TimeScaled>>,
+    parent_q: &Query<&Parent>,
+) -> bool {
+    let mut current_e = Some(e);
+
+    while let Some(e) = current_e {
+        if scaled_q.contains(e) {
+            return true;
+        }
+        current_e = parent_q.get(e).ok().map(|p| p.get());
+    }
+
+    false
+}
+
+fn fast_forward(actions: Res<Input<Action>>, mut time_scale: ResMut<TimeScale>) {
+    if actions.just_pressed(Action::FastForward) {
+        time_scale.speed = if time_scale.speed > 1. {
+            1.
+        } else {
+            FAST_FORWARD_SPEED
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(1., false, false => 1000)]
+    #[test_case(1., true, false => 350)]
+    #[test_case(4., false, true => 2400)]
+    fn scale_order_delta(speed: f32, slow_mo: bool, relaxed: bool) -> u32 {
+        let mut time_scale = TimeScale {
+            speed,
+            relaxed,
+            ..default()
+        };
+        if slow_mo {
+            time_scale.start_slow_mo();
+        }
+        time_scale.scale = time_scale.compute_scale();
+
+        let delta = time_scale.scale_order_delta(Duration::from_secs(1));
+        (delta.as_secs_f32() * 1000.).round() as u32
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{
+    board::BoardClear,
+    card::CardEffect,
+    input::Action,
+    interaction::{Interactable, InteractionGroup},
+    level::{CurrentLevel, InteractableSection, LevelEv, Levels},
+    localization::Localization,
+    render::ZIndex,
+    save::SaveData,
+    theme::{Theme, ThemeColor},
+    tile_placement::BoardEv,
+    ui::{spawn_panel, spawn_text, MENU_LINE_HEIGHT},
+    GameState,
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use iyes_loopless::prelude::*;
+
+pub struct TutorialPlugin;
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_exit_system(GameState::Playing, end_tutorial)
+            .add_system(start_tutorial.run_in_state(GameState::Playing))
+            .add_system(
+                update_tutorial
+                    .run_in_state(GameState::Playing)
+                    .after(start_tutorial),
+            );
+    }
+}
+
+const BUBBLE_CHAR_WIDTH: f32 = 4.;
+const BUBBLE_PADDING: f32 = 6.;
+const HIGHLIGHT_PADDING: f32 = 2.;
+
+/// What the player should look at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TutorialTarget {
+    None,
+    /// First interactable of the group
+    Group(InteractionGroup),
+    Section(usize),
+}
+
+/// What the player has to do to move to the next step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialTrigger {
+    Click,
+    PlacePiece,
+    /// Any row, column or section
+    ClearLine,
+    ClearSection,
+    DropIngredient,
+    BoostFire,
+}
+
+impl TutorialTrigger {
+    fn is_met_by_board(&self, ev: &BoardEv) -> bool {
+        match (self, ev) {
+            (TutorialTrigger::PlacePiece, BoardEv::PiecePlaced) => true,
+            (TutorialTrigger::ClearLine, BoardEv::Cleared(_)) => true,
+            (TutorialTrigger::ClearSection, BoardEv::Cleared(BoardClear::Section { .. })) => true,
+            _ => false,
+        }
+    }
+
+    fn is_met_by_card(&self, ev: &CardEffect) -> bool {
+        match (self, ev) {
+            (TutorialTrigger::DropIngredient, CardEffect::Ingredient { .. }) => true,
+            // the multiplier is only set by the finished soups, not by the cards
+            (
+                TutorialTrigger::BoostFire,
+                CardEffect::FireBoost {
+                    boost_dur_multiplier: None,
+                    ..
+                },
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TutorialStep {
+    pub target: TutorialTarget,
+    pub trigger: TutorialTrigger,
+    /// String table key
+    pub text: &'static str,
+    pub bubble_pos: Vec2,
+}
+
+impl TutorialStep {
+    pub fn new(
+        target: TutorialTarget,
+        trigger: TutorialTrigger,
+        text: &'static str,
+        bubble_pos: Vec2,
+    ) -> Self {
+        Self {
+            target,
+            trigger,
+            text,
+            bubble_pos,
+        }
+    }
+}
+
+pub struct ActiveTutorial {
+    steps: Vec<TutorialStep>,
+    index: usize,
+    bubble_e: Option<Entity>,
+    highlight_e: Option<Entity>,
+}
+
+impl ActiveTutorial {
+    fn get_step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.index)
+    }
+}
+
+/// The orders wait while the player reads the explanation steps
+pub fn is_tutorial_blocking(tutorial: Option<Res<ActiveTutorial>>) -> bool {
+    tutorial
+        .and_then(|t| t.get_step().map(|s| s.trigger == TutorialTrigger::Click))
+        .unwrap_or(false)
+}
+
+/// Outline with an arrow, a child of the target
+#[derive(Component)]
+struct TutorialHighlight;
+
+fn start_tutorial(
+    mut cmd: Commands,
+    mut lvl_evr: EventReader<LevelEv>,
+    lvl: Res<CurrentLevel>,
+    lvls: Res<Levels>,
+    save: Res<SaveData>,
+) {
+    for ev in lvl_evr.iter() {
+        // only until the day has been played
+        if let LevelEv::LevelStart = ev
+            && !lvl.retry
+            && !save.results.contains_key(&lvl.level_index)
+            && !lvls[lvl.level_index].tutorial.is_empty()
+        {
+            cmd.insert_resource(ActiveTutorial {
+                steps: lvls[lvl.level_index].tutorial.clone(),
+                index: 0,
+                bubble_e: None,
+                highlight_e: None,
+            });
+        }
+    }
+}
+
+fn update_tutorial(
+    mut cmd: Commands,
+    tutorial: Option<ResMut<ActiveTutorial>>,
+    mut lvl_evr: EventReader<LevelEv>,
+    mut board_evr: EventReader<BoardEv>,
+    mut card_evr: EventReader<CardEffect>,
+    actions: Res<Input<Action>>,
+    loc: Localization,
+    theme: Res<Theme>,
+    interactable_q: Query<(Entity, &Interactable)>,
+    section_q: Query<(Entity, &InteractableSection)>,
+    highlight_q: Query<(), With<TutorialHighlight>>,
+) {
+    let board_evs: Vec<_> = board_evr.iter().collect();
+    let card_evs: Vec<_> = card_evr.iter().collect();
+    let level_over = lvl_evr
+        .iter()
+        .any(|ev| matches!(ev, LevelEv::LevelOver { .. }));
+
+    let mut tutorial = match tutorial {
+        Some(tutorial) => tutorial,
+        None => return,
+    };
+
+    if level_over {
+        despawn_step(&mut cmd, &mut tutorial);
+        cmd.remove_resource::<ActiveTutorial>();
+        return;
+    }
+
+    let step = match tutorial.get_step() {
+        Some(step) => step.clone(),
+        None => {
+            cmd.remove_resource::<ActiveTutorial>();
+            return;
+        }
+    };
+
+    let done = match step.trigger {
+        TutorialTrigger::Click => actions.just_pressed(Action::Place),
+        trigger => {
+            board_evs.iter().any(|ev| trigger.is_met_by_board(ev))
+                || card_evs.iter().any(|ev| trigger.is_met_by_card(ev))
+        }
+    };
+
+    if done {
+        despawn_step(&mut cmd, &mut tutorial);
+        tutorial.index += 1;
+        return;
+    }
+
+    if tutorial.bubble_e.is_none() {
+        let text = loc.get(step.text);
+        let txt_e = spawn_text(
+            &mut cmd,
+            &loc,
+            text.clone(),
+            Vec3::new(0., 0., 0.1),
+            TextAlignment::CENTER,
+            ThemeColor::Light,
+        );
+        let bubble_e = spawn_panel(
+            &mut cmd,
+            &theme,
+            get_bubble_size(&text),
+            ZIndex::Tooltip,
+            "tutorial_bubble",
+            &[txt_e],
+        );
+        cmd.entity(bubble_e)
+            .insert(Transform::from_translation(step.bubble_pos.extend(0.)));
+        tutorial.bubble_e = Some(bubble_e);
+    }
+
+    // the target might not exist yet (no cards) or it might be gone (placed piece)
+    if tutorial
+        .highlight_e
+        .map_or(true, |e| !highlight_q.contains(e))
+    {
+        tutorial.highlight_e = match step.target {
+            TutorialTarget::None => None,
+            TutorialTarget::Group(group) => interactable_q
+                .iter()
+                .find(|(_, i)| i.group == group && i.enabled),
+            TutorialTarget::Section(index) => section_q
+                .iter()
+                .find(|(_, s)| s.0 == index)
+                .and_then(|(e, _)| interactable_q.get(e).ok()),
+        }
+        .map(|(e, interactable)| spawn_highlight(&mut cmd, &theme, e, interactable));
+    }
+}
+
+fn end_tutorial(mut cmd: Commands, tutorial: Option<ResMut<ActiveTutorial>>) {
+    if let Some(mut tutorial) = tutorial {
+        despawn_step(&mut cmd, &mut tutorial);
+        cmd.remove_resource::<ActiveTutorial>();
+    }
+}
+
+fn despawn_step(cmd: &mut Commands, tutorial: &mut ActiveTutorial) {
+    for e in [tutorial.bubble_e.take(), tutorial.highlight_e.take()]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(mut e_cmd) = cmd.get_entity(e) {
+            e_cmd.despawn_recursive();
+        }
+    }
+}
+
+fn spawn_highlight(
+    cmd: &mut Commands,
+    theme: &Theme,
+    target_e: Entity,
+    target: &Interactable,
+) -> Entity {
+    let bounds = target.bounds;
+    let center = (bounds.min + bounds.max) / 2.;
+    let draw_mode = DrawMode::Outlined {
+        fill_mode: FillMode::color(theme.get(ThemeColor::Highlighted)),
+        outline_mode: StrokeMode::new(theme.get(ThemeColor::Darker), 1.),
+    };
+
+    let arrow_e = cmd
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Polygon {
+                points: vec![Vec2::new(-5., 6.), Vec2::new(5., 6.), Vec2::new(0., -1.)],
+                closed: true,
+            },
+            draw_mode,
+            Transform::from_xyz(center.x, bounds.max.y + HIGHLIGHT_PADDING + 2., 0.),
+        ))
+        .id();
+
+    let highlight_e = cmd
+        .spawn_bundle(GeometryBuilder::build_as(
+            &shapes::Rectangle {
+                extents: bounds.max - bounds.min + HIGHLIGHT_PADDING * 2.,
+                origin: RectangleOrigin::CustomCenter(center),
+            },
+            DrawMode::Stroke(StrokeMode::new(theme.get(ThemeColor::Highlighted), 1.)),
+            Transform::from_xyz(0., 0., 1.),
+        ))
+        .insert(TutorialHighlight)
+        .insert(Name::new("tutorial_highlight"))
+        .add_child(arrow_e)
+        .id();
+
+    cmd.entity(target_e).add_child(highlight_e);
+    highlight_e
+}
+
+fn get_bubble_size(text: &str) -> Vec2 {
+    let line_len = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let line_count = text.lines().count().max(1);
+
+    Vec2::new(
+        line_len as f32 * BUBBLE_CHAR_WIDTH + BUBBLE_PADDING * 2.,
+        line_count as f32 * MENU_LINE_HEIGHT + BUBBLE_PADDING,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(TutorialTrigger::PlacePiece, BoardEv::PiecePlaced => true)]
+    #[test_case(TutorialTrigger::ClearLine, BoardEv::Cleared(BoardClear::Row(2)) => true)]
+    #[test_case(TutorialTrigger::ClearSection, BoardEv::Cleared(BoardClear::Column(1)) => false)]
+    #[test_case(TutorialTrigger::ClearSection, BoardEv::Cleared(BoardClear::Section { section_index: 4, used_special: false }) => true)]
+    #[test_case(TutorialTrigger::DropIngredient, BoardEv::PiecePlaced => false)]
+    fn is_met_by_board(trigger: TutorialTrigger, ev: BoardEv) -> bool {
+        trigger.is_met_by_board(&ev)
+    }
+
+    #[test_case(TutorialTrigger::BoostFire, None => true)]
+    #[test_case(TutorialTrigger::BoostFire, Some(2.) => false)]
+    #[test_case(TutorialTrigger::DropIngredient, None => false)]
+    fn is_met_by_card(trigger: TutorialTrigger, boost_dur_multiplier: Option<f32>) -> bool {
+        trigger.is_met_by_card(&CardEffect::FireBoost {
+            cauldron_e: Entity::from_raw(0),
+            boost_dur_multiplier,
+        })
+    }
+
+    #[test_case("Hello" => (32., 18.))]
+    #[test_case("Two\nlines!" => (36., 30.))]
+    fn get_bubble_size(text: &str) -> (f32, f32) {
+        let size = super::get_bubble_size(text);
+        (size.x, size.y)
+    }
+}
@@ -23,6 +23,19 @@ impl Plugin for GameTweenPlugin {
     }
 }
 
+/// Movement tweens are still created (so their completion actions fire), but finish right away
+const REDUCED_MOTION_MOVE_MS: u64 = 1;
+/// Fade used instead of a slide in the reduced motion mode
+pub const REDUCED_MOTION_FADE_MS: u64 = 250;
+
+fn get_motion_duration(duration_ms: u64, reduced_motion: bool) -> Duration {
+    Duration::from_millis(if reduced_motion {
+        duration_ms.min(REDUCED_MOTION_MOVE_MS)
+    } else {
+        duration_ms
+    })
+}
+
 #[repr(u64)]
 #[derive(Clone)]
 pub enum TweenDoneAction {
@@ -245,19 +258,26 @@ pub fn delay_tween<T: 'static>(tween: Tween<T>, delay_ms: u64) -> Sequence<T> {
 pub fn get_relative_move_anim(
     end_pos: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     on_completed: Option<TweenDoneAction>,
 ) -> Animator<Transform> {
-    Animator::new(get_relative_move_tween(end_pos, duration_ms, on_completed))
+    Animator::new(get_relative_move_tween(
+        end_pos,
+        duration_ms,
+        reduced_motion,
+        on_completed,
+    ))
 }
 
 pub fn get_relative_move_tween(
     end_pos: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     on_completed: Option<TweenDoneAction>,
 ) -> Tween<Transform> {
     let mut tween = Tween::new(
         EaseFunction::QuadraticInOut,
-        Duration::from_millis(duration_ms),
+        get_motion_duration(duration_ms, reduced_motion),
         TransformRelativePositionLens {
             start: Vec3::ZERO,
             end: end_pos,
@@ -275,6 +295,7 @@ pub fn get_move_anim(
     start_pos: Vec3,
     end_pos: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     ease: EaseFunction,
     on_completed: Option<TweenDoneAction>,
 ) -> Animator<Transform> {
@@ -282,6 +303,7 @@ pub fn get_move_anim(
         start_pos,
         end_pos,
         duration_ms,
+        reduced_motion,
         ease,
         on_completed,
     ))
@@ -290,11 +312,13 @@ pub fn get_move_anim(
 pub fn get_relative_move_by_anim(
     move_by: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     on_completed: Option<TweenDoneAction>,
 ) -> Animator<Transform> {
     Animator::new(get_relative_move_by_tween(
         move_by,
         duration_ms,
+        reduced_motion,
         EaseFunction::QuadraticInOut,
         on_completed,
     ))
@@ -303,12 +327,13 @@ pub fn get_relative_move_by_anim(
 pub fn get_relative_move_by_tween(
     move_by: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     ease: EaseFunction,
     on_completed: Option<TweenDoneAction>,
 ) -> Tween<Transform> {
     let mut tween = Tween::new(
         ease,
-        Duration::from_millis(duration_ms),
+        get_motion_duration(duration_ms, reduced_motion),
         TransformRelativeByPositionLens::new(move_by),
     );
 
@@ -323,12 +348,13 @@ pub fn get_move_tween(
     start_pos: Vec3,
     end_pos: Vec3,
     duration_ms: u64,
+    reduced_motion: bool,
     ease: EaseFunction,
     on_completed: Option<TweenDoneAction>,
 ) -> Tween<Transform> {
     let mut tween = Tween::new(
         ease,
-        Duration::from_millis(duration_ms),
+        get_motion_duration(duration_ms, reduced_motion),
         TransformPositionLens {
             start: start_pos,
             end: end_pos,
@@ -448,11 +474,12 @@ pub fn get_scale_tween(
     end_scale: Vec3,
     ease: EaseFunction,
     duration_ms: u64,
+    reduced_motion: bool,
     on_completed: Option<TweenDoneAction>,
 ) -> Tween<Transform> {
     let mut tween = Tween::new(
         ease,
-        Duration::from_millis(duration_ms),
+        get_motion_duration(duration_ms, reduced_motion),
         TransformScaleLens {
             start: start_scale,
             end: end_scale,
@@ -630,3 +657,16 @@ impl Lens<Text> for TextRelativeColorLens {
         self.start = target.sections.iter().map(|s| s.style.color).collect();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(450, false => 450)]
+    #[test_case(450, true => REDUCED_MOTION_MOVE_MS)]
+    #[test_case(0, true => 0)]
+    fn get_motion_duration(duration_ms: u64, reduced_motion: bool) -> u64 {
+        super::get_motion_duration(duration_ms, reduced_motion).as_millis() as u64
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    input::Action,
+    interaction::{Interactable, InteractionEv, InteractionGroup, InteractionState},
+    localization::Localization,
+    render::ZIndex,
+    theme::{Theme, ThemeColor, ThemedText},
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use iyes_loopless::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI};
+
+pub struct UiPlugin;
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_loopless_state(Overlay::None)
+            .add_event::<ButtonClickEv>()
+            .add_system(click_button)
+            .add_system(highlight_button_on_hover);
+    }
+}
+
+pub const MENU_FONT_SIZE: f32 = 16.;
+pub const MENU_LINE_HEIGHT: f32 = 12.;
+
+/// Screen shown over the game - gameplay is paused while it's open
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Overlay {
+    None,
+    Pause,
+    Controls,
+    Settings,
+}
+
+#[derive(Component)]
+pub struct MenuButton {
+    pub enabled: bool,
+    color: ThemeColor,
+}
+
+pub struct ButtonClickEv(pub Entity);
+
+pub fn spawn_text(
+    cmd: &mut Commands,
+    loc: &Localization,
+    text: impl Into<String>,
+    position: Vec3,
+    alignment: TextAlignment,
+    color: ThemeColor,
+) -> Entity {
+    cmd.spawn_bundle(Text2dBundle {
+        text: Text::from_section(
+            text,
+            TextStyle {
+                font: loc.get_font(),
+                font_size: MENU_FONT_SIZE,
+                // set by the theme
+                color: Color::NONE,
+            },
+        )
+        .with_alignment(alignment),
+        transform: Transform::from_translation(position),
+        ..default()
+    })
+    .insert(ThemedText(color))
+    .id()
+}
+
+/// Clickable text - the bounds are set based on the text alignment as the text size isn't known upfront.
+pub fn spawn_button(
+    cmd: &mut Commands,
+    loc: &Localization,
+    text: impl Into<String>,
+    position: Vec3,
+    alignment: TextAlignment,
+    width: f32,
+    color: ThemeColor,
+    enabled: bool,
+) -> Entity {
+    let h = MENU_LINE_HEIGHT / 2.;
+    let (min_x, max_x) = match alignment.horizontal {
+        HorizontalAlign::Left => (0., width),
+        HorizontalAlign::Center => (-width / 2., width / 2.),
+        HorizontalAlign::Right => (-width, 0.),
+    };
+
+    let e = spawn_text(cmd, loc, text, position, alignment, color);
+    cmd.entity(e)
+        .insert(Interactable::new(
+            InteractionGroup::Button,
+            Vec2::new(min_x, -h),
+            Vec2::new(max_x, h),
+        ))
+        .insert(MenuButton { enabled, color })
+        .insert(Name::new("button"));
+
+    e
+}
+
+pub fn spawn_panel(
+    cmd: &mut Commands,
+    theme: &Theme,
+    size: Vec2,
+    z_index: ZIndex,
+    name: &'static str,
+    children: &[Entity],
+) -> Entity {
+    cmd.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(size),
+            color: theme.get(ThemeColor::Panel),
+            ..default()
+        },
+        ..default()
+    })
+    .insert(z_index)
+    .insert(Name::new(name))
+    .push_children(children)
+    .id()
+}
+
+pub fn get_star_shape(position: Vec3, radius: f32, draw_mode: DrawMode) -> ShapeBundle {
+    let points = (0..10)
+        .map(|i| {
+            let r = if i % 2 == 0 { radius } else { radius * 0.45 };
+            let angle = FRAC_PI_2 + i as f32 * PI / 5.;
+            Vec2::new(angle.cos(), angle.sin()) * r
+        })
+        .collect();
+
+    GeometryBuilder::build_as(
+        &shapes::Polygon {
+            points,
+            closed: true,
+        },
+        draw_mode,
+        Transform::from_translation(position),
+    )
+}
+
+/// Missing stars are just outlined so they don't differ by color only, `None` hides the star
+pub fn get_star_draw_mode(earned: Option<bool>, theme: &Theme) -> DrawMode {
+    match earned {
+        Some(true) => DrawMode::Fill(FillMode::color(theme.get(ThemeColor::Highlighted))),
+        Some(false) => DrawMode::Stroke(StrokeMode::new(theme.get(ThemeColor::Dark), 1.)),
+        None => DrawMode::Fill(FillMode::color(Color::NONE)),
+    }
+}
+
+fn click_button(
+    actions: Res<Input<Action>>,
+    state: Res<InteractionState>,
+    button_q: Query<&MenuButton>,
+    mut evw: EventWriter<ButtonClickEv>,
+) {
+    if actions.just_pressed(Action::Place) {
+        for e in state.get_hovered_entities(&InteractionGroup::Button) {
+            if let Ok(button) = button_q.get(e) && button.enabled {
+                evw.send(ButtonClickEv(e));
+            }
+        }
+    }
+}
+
+fn highlight_button_on_hover(
+    mut evr: EventReader<InteractionEv>,
+    mut button_q: Query<(&MenuButton, &mut Text)>,
+    theme: Res<Theme>,
+) {
+    for ev in evr.iter() {
+        if let Some((e, start)) = match ev {
+            InteractionEv::HoverStart(data) => Some((data.e, true)),
+            InteractionEv::HoverEnd(data) => Some((data.e, false)),
+            _ => None,
+        } {
+            if let Ok((button, mut txt)) = button_q.get_mut(e) && button.enabled {
+                let col = theme.get(if start {
+                    ThemeColor::Highlighted
+                } else {
+                    button.color
+                });
+
+                for s in txt.sections.iter_mut() {
+                    s.style.color = col;
+                }
+            }
+        }
+    }
+}